@@ -26,12 +26,16 @@ Register-ArgumentCompleter -Native -CommandName 'rpk' -ScriptBlock {
             [CompletionResult]::new('--data-dir', '--data-dir', [CompletionResultType]::ParameterName, 'The directory to store package data')
             [CompletionResult]::new('--cache-dir', '--cache-dir', [CompletionResultType]::ParameterName, 'The directory to store downloaded packages')
             [CompletionResult]::new('--bin-dir', '--bin-dir', [CompletionResultType]::ParameterName, 'The directory installed binaries linked to')
+            [CompletionResult]::new('--share-dir', '--share-dir', [CompletionResultType]::ParameterName, 'The directory installed man pages and shell completions linked to')
+            [CompletionResult]::new('--max-extract-size', '--max-extract-size', [CompletionResultType]::ParameterName, 'The maximum total size of the files extracted from an archive')
+            [CompletionResult]::new('--max-extract-files', '--max-extract-files', [CompletionResultType]::ParameterName, 'The maximum number of the entries extracted from an archive')
+            [CompletionResult]::new('--target', '--target', [CompletionResultType]::ParameterName, 'The platform to resolve assets for instead of the current one, without installing them')
             [CompletionResult]::new('-q', '-q', [CompletionResultType]::ParameterName, 'Suppress any informational output')
             [CompletionResult]::new('--quiet', '--quiet', [CompletionResultType]::ParameterName, 'Suppress any informational output')
             [CompletionResult]::new('-v', '-v', [CompletionResultType]::ParameterName, 'Use verbose output')
             [CompletionResult]::new('--verbose', '--verbose', [CompletionResultType]::ParameterName, 'Use verbose output')
-            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
-            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
             [CompletionResult]::new('-V', '-V ', [CompletionResultType]::ParameterName, 'Print version')
             [CompletionResult]::new('--version', '--version', [CompletionResultType]::ParameterName, 'Print version')
             [CompletionResult]::new('init', 'init', [CompletionResultType]::ParameterValue, 'Initialize a configuration file')
@@ -42,13 +46,18 @@ Register-ArgumentCompleter -Native -CommandName 'rpk' -ScriptBlock {
             [CompletionResult]::new('s', 's', [CompletionResultType]::ParameterValue, 'Install any missing packages, re-generating the lock file')
             [CompletionResult]::new('add', 'add', [CompletionResultType]::ParameterValue, 'Add a new plugin to the config file')
             [CompletionResult]::new('a', 'a', [CompletionResultType]::ParameterValue, 'Add a new plugin to the config file')
+            [CompletionResult]::new('remove', 'remove', [CompletionResultType]::ParameterValue, 'Remove packages from the config file and uninstall them')
+            [CompletionResult]::new('rm', 'rm', [CompletionResultType]::ParameterValue, 'Remove packages from the config file and uninstall them')
             [CompletionResult]::new('restore', 'restore', [CompletionResultType]::ParameterValue, 'Restore packages to the state in the lockfile')
             [CompletionResult]::new('r', 'r', [CompletionResultType]::ParameterValue, 'Restore packages to the state in the lockfile')
             [CompletionResult]::new('update', 'update', [CompletionResultType]::ParameterValue, 'Update packages and re-generate the lock file')
             [CompletionResult]::new('u', 'u', [CompletionResultType]::ParameterValue, 'Update packages and re-generate the lock file')
+            [CompletionResult]::new('outdated', 'outdated', [CompletionResultType]::ParameterValue, 'List packages which have a different version available, without installing anything')
+            [CompletionResult]::new('o', 'o', [CompletionResultType]::ParameterValue, 'List packages which have a different version available, without installing anything')
             [CompletionResult]::new('find', 'find', [CompletionResultType]::ParameterValue, 'Find packages matching the given query')
             [CompletionResult]::new('f', 'f', [CompletionResultType]::ParameterValue, 'Find packages matching the given query')
             [CompletionResult]::new('fd', 'fd', [CompletionResultType]::ParameterValue, 'Find packages matching the given query')
+            [CompletionResult]::new('use', 'use', [CompletionResultType]::ParameterValue, 'Switch a package to a version kept on disk, without network access')
             [CompletionResult]::new('cleanup', 'cleanup', [CompletionResultType]::ParameterValue, 'Remove packages which are not listed in the lock file')
             [CompletionResult]::new('env', 'env', [CompletionResultType]::ParameterValue, 'Prints the environment variables for rpk')
             [CompletionResult]::new('completions', 'completions', [CompletionResultType]::ParameterValue, 'Generate completions for the given shell')
@@ -60,69 +69,81 @@ Register-ArgumentCompleter -Native -CommandName 'rpk' -ScriptBlock {
             [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'The config file URL to initialize from')
             [CompletionResult]::new('--from', '--from', [CompletionResultType]::ParameterName, 'The config file URL to initialize from')
             [CompletionResult]::new('--color', '--color', [CompletionResultType]::ParameterName, 'This flag controls when to use colors')
+            [CompletionResult]::new('--target', '--target', [CompletionResultType]::ParameterName, 'The platform to resolve assets for instead of the current one, without installing them')
             [CompletionResult]::new('-q', '-q', [CompletionResultType]::ParameterName, 'Suppress any informational output')
             [CompletionResult]::new('--quiet', '--quiet', [CompletionResultType]::ParameterName, 'Suppress any informational output')
             [CompletionResult]::new('-v', '-v', [CompletionResultType]::ParameterName, 'Use verbose output')
             [CompletionResult]::new('--verbose', '--verbose', [CompletionResultType]::ParameterName, 'Use verbose output')
-            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
-            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
             break
         }
         'rpk;list' {
             [CompletionResult]::new('--color', '--color', [CompletionResultType]::ParameterName, 'This flag controls when to use colors')
+            [CompletionResult]::new('--target', '--target', [CompletionResultType]::ParameterName, 'The platform to resolve assets for instead of the current one, without installing them')
+            [CompletionResult]::new('--all-versions', '--all-versions', [CompletionResultType]::ParameterName, 'List all versions kept on disk instead of the locked ones')
             [CompletionResult]::new('-q', '-q', [CompletionResultType]::ParameterName, 'Suppress any informational output')
             [CompletionResult]::new('--quiet', '--quiet', [CompletionResultType]::ParameterName, 'Suppress any informational output')
             [CompletionResult]::new('-v', '-v', [CompletionResultType]::ParameterName, 'Use verbose output')
             [CompletionResult]::new('--verbose', '--verbose', [CompletionResultType]::ParameterName, 'Use verbose output')
-            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
-            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
             break
         }
         'rpk;l' {
             [CompletionResult]::new('--color', '--color', [CompletionResultType]::ParameterName, 'This flag controls when to use colors')
+            [CompletionResult]::new('--target', '--target', [CompletionResultType]::ParameterName, 'The platform to resolve assets for instead of the current one, without installing them')
+            [CompletionResult]::new('--all-versions', '--all-versions', [CompletionResultType]::ParameterName, 'List all versions kept on disk instead of the locked ones')
             [CompletionResult]::new('-q', '-q', [CompletionResultType]::ParameterName, 'Suppress any informational output')
             [CompletionResult]::new('--quiet', '--quiet', [CompletionResultType]::ParameterName, 'Suppress any informational output')
             [CompletionResult]::new('-v', '-v', [CompletionResultType]::ParameterName, 'Use verbose output')
             [CompletionResult]::new('--verbose', '--verbose', [CompletionResultType]::ParameterName, 'Use verbose output')
-            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
-            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
             break
         }
         'rpk;ls' {
             [CompletionResult]::new('--color', '--color', [CompletionResultType]::ParameterName, 'This flag controls when to use colors')
+            [CompletionResult]::new('--target', '--target', [CompletionResultType]::ParameterName, 'The platform to resolve assets for instead of the current one, without installing them')
+            [CompletionResult]::new('--all-versions', '--all-versions', [CompletionResultType]::ParameterName, 'List all versions kept on disk instead of the locked ones')
             [CompletionResult]::new('-q', '-q', [CompletionResultType]::ParameterName, 'Suppress any informational output')
             [CompletionResult]::new('--quiet', '--quiet', [CompletionResultType]::ParameterName, 'Suppress any informational output')
             [CompletionResult]::new('-v', '-v', [CompletionResultType]::ParameterName, 'Use verbose output')
             [CompletionResult]::new('--verbose', '--verbose', [CompletionResultType]::ParameterName, 'Use verbose output')
-            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
-            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
             break
         }
         'rpk;sync' {
             [CompletionResult]::new('--color', '--color', [CompletionResultType]::ParameterName, 'This flag controls when to use colors')
+            [CompletionResult]::new('--target', '--target', [CompletionResultType]::ParameterName, 'The platform to resolve assets for instead of the current one, without installing them')
             [CompletionResult]::new('-q', '-q', [CompletionResultType]::ParameterName, 'Suppress any informational output')
             [CompletionResult]::new('--quiet', '--quiet', [CompletionResultType]::ParameterName, 'Suppress any informational output')
             [CompletionResult]::new('-v', '-v', [CompletionResultType]::ParameterName, 'Use verbose output')
             [CompletionResult]::new('--verbose', '--verbose', [CompletionResultType]::ParameterName, 'Use verbose output')
-            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
-            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
             break
         }
         'rpk;s' {
             [CompletionResult]::new('--color', '--color', [CompletionResultType]::ParameterName, 'This flag controls when to use colors')
+            [CompletionResult]::new('--target', '--target', [CompletionResultType]::ParameterName, 'The platform to resolve assets for instead of the current one, without installing them')
             [CompletionResult]::new('-q', '-q', [CompletionResultType]::ParameterName, 'Suppress any informational output')
             [CompletionResult]::new('--quiet', '--quiet', [CompletionResultType]::ParameterName, 'Suppress any informational output')
             [CompletionResult]::new('-v', '-v', [CompletionResultType]::ParameterName, 'Use verbose output')
             [CompletionResult]::new('--verbose', '--verbose', [CompletionResultType]::ParameterName, 'Use verbose output')
-            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
-            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
             break
         }
         'rpk;add' {
+            [CompletionResult]::new('--source', '--source', [CompletionResultType]::ParameterName, 'The source hosting the repository')
+            [CompletionResult]::new('--host', '--host', [CompletionResultType]::ParameterName, 'The base URL of a self-managed instance of the source')
             [CompletionResult]::new('--name', '--name', [CompletionResultType]::ParameterName, 'A unique name for the package. Defaults to the repo name')
             [CompletionResult]::new('--version', '--version', [CompletionResultType]::ParameterName, 'The version of the package')
             [CompletionResult]::new('--desc', '--desc', [CompletionResultType]::ParameterName, 'A description of the package')
             [CompletionResult]::new('--color', '--color', [CompletionResultType]::ParameterName, 'This flag controls when to use colors')
+            [CompletionResult]::new('--target', '--target', [CompletionResultType]::ParameterName, 'The platform to resolve assets for instead of the current one, without installing them')
             [CompletionResult]::new('-q', '-q', [CompletionResultType]::ParameterName, 'Suppress any informational output')
             [CompletionResult]::new('--quiet', '--quiet', [CompletionResultType]::ParameterName, 'Suppress any informational output')
             [CompletionResult]::new('-v', '-v', [CompletionResultType]::ParameterName, 'Use verbose output')
@@ -132,10 +153,37 @@ Register-ArgumentCompleter -Native -CommandName 'rpk' -ScriptBlock {
             break
         }
         'rpk;a' {
+            [CompletionResult]::new('--source', '--source', [CompletionResultType]::ParameterName, 'The source hosting the repository')
+            [CompletionResult]::new('--host', '--host', [CompletionResultType]::ParameterName, 'The base URL of a self-managed instance of the source')
             [CompletionResult]::new('--name', '--name', [CompletionResultType]::ParameterName, 'A unique name for the package. Defaults to the repo name')
             [CompletionResult]::new('--version', '--version', [CompletionResultType]::ParameterName, 'The version of the package')
             [CompletionResult]::new('--desc', '--desc', [CompletionResultType]::ParameterName, 'A description of the package')
             [CompletionResult]::new('--color', '--color', [CompletionResultType]::ParameterName, 'This flag controls when to use colors')
+            [CompletionResult]::new('--target', '--target', [CompletionResultType]::ParameterName, 'The platform to resolve assets for instead of the current one, without installing them')
+            [CompletionResult]::new('-q', '-q', [CompletionResultType]::ParameterName, 'Suppress any informational output')
+            [CompletionResult]::new('--quiet', '--quiet', [CompletionResultType]::ParameterName, 'Suppress any informational output')
+            [CompletionResult]::new('-v', '-v', [CompletionResultType]::ParameterName, 'Use verbose output')
+            [CompletionResult]::new('--verbose', '--verbose', [CompletionResultType]::ParameterName, 'Use verbose output')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            break
+        }
+        'rpk;remove' {
+            [CompletionResult]::new('--color', '--color', [CompletionResultType]::ParameterName, 'This flag controls when to use colors')
+            [CompletionResult]::new('--target', '--target', [CompletionResultType]::ParameterName, 'The platform to resolve assets for instead of the current one, without installing them')
+            [CompletionResult]::new('--cache', '--cache', [CompletionResultType]::ParameterName, 'Remove the cached assets of the packages as well')
+            [CompletionResult]::new('-q', '-q', [CompletionResultType]::ParameterName, 'Suppress any informational output')
+            [CompletionResult]::new('--quiet', '--quiet', [CompletionResultType]::ParameterName, 'Suppress any informational output')
+            [CompletionResult]::new('-v', '-v', [CompletionResultType]::ParameterName, 'Use verbose output')
+            [CompletionResult]::new('--verbose', '--verbose', [CompletionResultType]::ParameterName, 'Use verbose output')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            break
+        }
+        'rpk;rm' {
+            [CompletionResult]::new('--color', '--color', [CompletionResultType]::ParameterName, 'This flag controls when to use colors')
+            [CompletionResult]::new('--target', '--target', [CompletionResultType]::ParameterName, 'The platform to resolve assets for instead of the current one, without installing them')
+            [CompletionResult]::new('--cache', '--cache', [CompletionResultType]::ParameterName, 'Remove the cached assets of the packages as well')
             [CompletionResult]::new('-q', '-q', [CompletionResultType]::ParameterName, 'Suppress any informational output')
             [CompletionResult]::new('--quiet', '--quiet', [CompletionResultType]::ParameterName, 'Suppress any informational output')
             [CompletionResult]::new('-v', '-v', [CompletionResultType]::ParameterName, 'Use verbose output')
@@ -146,102 +194,146 @@ Register-ArgumentCompleter -Native -CommandName 'rpk' -ScriptBlock {
         }
         'rpk;restore' {
             [CompletionResult]::new('--color', '--color', [CompletionResultType]::ParameterName, 'This flag controls when to use colors')
+            [CompletionResult]::new('--target', '--target', [CompletionResultType]::ParameterName, 'The platform to resolve assets for instead of the current one, without installing them')
             [CompletionResult]::new('-q', '-q', [CompletionResultType]::ParameterName, 'Suppress any informational output')
             [CompletionResult]::new('--quiet', '--quiet', [CompletionResultType]::ParameterName, 'Suppress any informational output')
             [CompletionResult]::new('-v', '-v', [CompletionResultType]::ParameterName, 'Use verbose output')
             [CompletionResult]::new('--verbose', '--verbose', [CompletionResultType]::ParameterName, 'Use verbose output')
-            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
-            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
             break
         }
         'rpk;r' {
             [CompletionResult]::new('--color', '--color', [CompletionResultType]::ParameterName, 'This flag controls when to use colors')
+            [CompletionResult]::new('--target', '--target', [CompletionResultType]::ParameterName, 'The platform to resolve assets for instead of the current one, without installing them')
             [CompletionResult]::new('-q', '-q', [CompletionResultType]::ParameterName, 'Suppress any informational output')
             [CompletionResult]::new('--quiet', '--quiet', [CompletionResultType]::ParameterName, 'Suppress any informational output')
             [CompletionResult]::new('-v', '-v', [CompletionResultType]::ParameterName, 'Use verbose output')
             [CompletionResult]::new('--verbose', '--verbose', [CompletionResultType]::ParameterName, 'Use verbose output')
-            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
-            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
             break
         }
         'rpk;update' {
             [CompletionResult]::new('--color', '--color', [CompletionResultType]::ParameterName, 'This flag controls when to use colors')
+            [CompletionResult]::new('--target', '--target', [CompletionResultType]::ParameterName, 'The platform to resolve assets for instead of the current one, without installing them')
             [CompletionResult]::new('-q', '-q', [CompletionResultType]::ParameterName, 'Suppress any informational output')
             [CompletionResult]::new('--quiet', '--quiet', [CompletionResultType]::ParameterName, 'Suppress any informational output')
             [CompletionResult]::new('-v', '-v', [CompletionResultType]::ParameterName, 'Use verbose output')
             [CompletionResult]::new('--verbose', '--verbose', [CompletionResultType]::ParameterName, 'Use verbose output')
-            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
-            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
             break
         }
         'rpk;u' {
             [CompletionResult]::new('--color', '--color', [CompletionResultType]::ParameterName, 'This flag controls when to use colors')
+            [CompletionResult]::new('--target', '--target', [CompletionResultType]::ParameterName, 'The platform to resolve assets for instead of the current one, without installing them')
             [CompletionResult]::new('-q', '-q', [CompletionResultType]::ParameterName, 'Suppress any informational output')
             [CompletionResult]::new('--quiet', '--quiet', [CompletionResultType]::ParameterName, 'Suppress any informational output')
             [CompletionResult]::new('-v', '-v', [CompletionResultType]::ParameterName, 'Use verbose output')
             [CompletionResult]::new('--verbose', '--verbose', [CompletionResultType]::ParameterName, 'Use verbose output')
-            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
-            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            break
+        }
+        'rpk;outdated' {
+            [CompletionResult]::new('--color', '--color', [CompletionResultType]::ParameterName, 'This flag controls when to use colors')
+            [CompletionResult]::new('--target', '--target', [CompletionResultType]::ParameterName, 'The platform to resolve assets for instead of the current one, without installing them')
+            [CompletionResult]::new('-q', '-q', [CompletionResultType]::ParameterName, 'Suppress any informational output')
+            [CompletionResult]::new('--quiet', '--quiet', [CompletionResultType]::ParameterName, 'Suppress any informational output')
+            [CompletionResult]::new('-v', '-v', [CompletionResultType]::ParameterName, 'Use verbose output')
+            [CompletionResult]::new('--verbose', '--verbose', [CompletionResultType]::ParameterName, 'Use verbose output')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            break
+        }
+        'rpk;o' {
+            [CompletionResult]::new('--color', '--color', [CompletionResultType]::ParameterName, 'This flag controls when to use colors')
+            [CompletionResult]::new('--target', '--target', [CompletionResultType]::ParameterName, 'The platform to resolve assets for instead of the current one, without installing them')
+            [CompletionResult]::new('-q', '-q', [CompletionResultType]::ParameterName, 'Suppress any informational output')
+            [CompletionResult]::new('--quiet', '--quiet', [CompletionResultType]::ParameterName, 'Suppress any informational output')
+            [CompletionResult]::new('-v', '-v', [CompletionResultType]::ParameterName, 'Use verbose output')
+            [CompletionResult]::new('--verbose', '--verbose', [CompletionResultType]::ParameterName, 'Use verbose output')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
             break
         }
         'rpk;find' {
             [CompletionResult]::new('--top', '--top', [CompletionResultType]::ParameterName, 'The number of results to display')
             [CompletionResult]::new('--color', '--color', [CompletionResultType]::ParameterName, 'This flag controls when to use colors')
+            [CompletionResult]::new('--target', '--target', [CompletionResultType]::ParameterName, 'The platform to resolve assets for instead of the current one, without installing them')
             [CompletionResult]::new('-q', '-q', [CompletionResultType]::ParameterName, 'Suppress any informational output')
             [CompletionResult]::new('--quiet', '--quiet', [CompletionResultType]::ParameterName, 'Suppress any informational output')
             [CompletionResult]::new('-v', '-v', [CompletionResultType]::ParameterName, 'Use verbose output')
             [CompletionResult]::new('--verbose', '--verbose', [CompletionResultType]::ParameterName, 'Use verbose output')
-            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
-            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
             break
         }
         'rpk;f' {
             [CompletionResult]::new('--top', '--top', [CompletionResultType]::ParameterName, 'The number of results to display')
             [CompletionResult]::new('--color', '--color', [CompletionResultType]::ParameterName, 'This flag controls when to use colors')
+            [CompletionResult]::new('--target', '--target', [CompletionResultType]::ParameterName, 'The platform to resolve assets for instead of the current one, without installing them')
             [CompletionResult]::new('-q', '-q', [CompletionResultType]::ParameterName, 'Suppress any informational output')
             [CompletionResult]::new('--quiet', '--quiet', [CompletionResultType]::ParameterName, 'Suppress any informational output')
             [CompletionResult]::new('-v', '-v', [CompletionResultType]::ParameterName, 'Use verbose output')
             [CompletionResult]::new('--verbose', '--verbose', [CompletionResultType]::ParameterName, 'Use verbose output')
-            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
-            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
             break
         }
         'rpk;fd' {
             [CompletionResult]::new('--top', '--top', [CompletionResultType]::ParameterName, 'The number of results to display')
             [CompletionResult]::new('--color', '--color', [CompletionResultType]::ParameterName, 'This flag controls when to use colors')
+            [CompletionResult]::new('--target', '--target', [CompletionResultType]::ParameterName, 'The platform to resolve assets for instead of the current one, without installing them')
+            [CompletionResult]::new('-q', '-q', [CompletionResultType]::ParameterName, 'Suppress any informational output')
+            [CompletionResult]::new('--quiet', '--quiet', [CompletionResultType]::ParameterName, 'Suppress any informational output')
+            [CompletionResult]::new('-v', '-v', [CompletionResultType]::ParameterName, 'Use verbose output')
+            [CompletionResult]::new('--verbose', '--verbose', [CompletionResultType]::ParameterName, 'Use verbose output')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            break
+        }
+        'rpk;use' {
+            [CompletionResult]::new('--color', '--color', [CompletionResultType]::ParameterName, 'This flag controls when to use colors')
+            [CompletionResult]::new('--target', '--target', [CompletionResultType]::ParameterName, 'The platform to resolve assets for instead of the current one, without installing them')
             [CompletionResult]::new('-q', '-q', [CompletionResultType]::ParameterName, 'Suppress any informational output')
             [CompletionResult]::new('--quiet', '--quiet', [CompletionResultType]::ParameterName, 'Suppress any informational output')
             [CompletionResult]::new('-v', '-v', [CompletionResultType]::ParameterName, 'Use verbose output')
             [CompletionResult]::new('--verbose', '--verbose', [CompletionResultType]::ParameterName, 'Use verbose output')
-            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
-            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
             break
         }
         'rpk;cleanup' {
+            [CompletionResult]::new('--keep', '--keep', [CompletionResultType]::ParameterName, 'The number of the most recently installed versions of each package to keep besides the locked one')
             [CompletionResult]::new('--color', '--color', [CompletionResultType]::ParameterName, 'This flag controls when to use colors')
+            [CompletionResult]::new('--target', '--target', [CompletionResultType]::ParameterName, 'The platform to resolve assets for instead of the current one, without installing them')
             [CompletionResult]::new('--cache', '--cache', [CompletionResultType]::ParameterName, 'Remove all cached data as well')
             [CompletionResult]::new('-q', '-q', [CompletionResultType]::ParameterName, 'Suppress any informational output')
             [CompletionResult]::new('--quiet', '--quiet', [CompletionResultType]::ParameterName, 'Suppress any informational output')
             [CompletionResult]::new('-v', '-v', [CompletionResultType]::ParameterName, 'Use verbose output')
             [CompletionResult]::new('--verbose', '--verbose', [CompletionResultType]::ParameterName, 'Use verbose output')
-            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
-            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
             break
         }
         'rpk;env' {
             [CompletionResult]::new('--color', '--color', [CompletionResultType]::ParameterName, 'This flag controls when to use colors')
+            [CompletionResult]::new('--target', '--target', [CompletionResultType]::ParameterName, 'The platform to resolve assets for instead of the current one, without installing them')
             [CompletionResult]::new('-q', '-q', [CompletionResultType]::ParameterName, 'Suppress any informational output')
             [CompletionResult]::new('--quiet', '--quiet', [CompletionResultType]::ParameterName, 'Suppress any informational output')
             [CompletionResult]::new('-v', '-v', [CompletionResultType]::ParameterName, 'Use verbose output')
             [CompletionResult]::new('--verbose', '--verbose', [CompletionResultType]::ParameterName, 'Use verbose output')
-            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
-            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
             break
         }
         'rpk;completions' {
             [CompletionResult]::new('-d', '-d', [CompletionResultType]::ParameterName, 'The directory to write the completions to')
             [CompletionResult]::new('--dir', '--dir', [CompletionResultType]::ParameterName, 'The directory to write the completions to')
             [CompletionResult]::new('--color', '--color', [CompletionResultType]::ParameterName, 'This flag controls when to use colors')
+            [CompletionResult]::new('--target', '--target', [CompletionResultType]::ParameterName, 'The platform to resolve assets for instead of the current one, without installing them')
             [CompletionResult]::new('-l', '-l', [CompletionResultType]::ParameterName, 'List all available shells')
             [CompletionResult]::new('--list', '--list', [CompletionResultType]::ParameterName, 'List all available shells')
             [CompletionResult]::new('-q', '-q', [CompletionResultType]::ParameterName, 'Suppress any informational output')
@@ -254,12 +346,13 @@ Register-ArgumentCompleter -Native -CommandName 'rpk' -ScriptBlock {
         }
         'rpk;version' {
             [CompletionResult]::new('--color', '--color', [CompletionResultType]::ParameterName, 'This flag controls when to use colors')
+            [CompletionResult]::new('--target', '--target', [CompletionResultType]::ParameterName, 'The platform to resolve assets for instead of the current one, without installing them')
             [CompletionResult]::new('-q', '-q', [CompletionResultType]::ParameterName, 'Suppress any informational output')
             [CompletionResult]::new('--quiet', '--quiet', [CompletionResultType]::ParameterName, 'Suppress any informational output')
             [CompletionResult]::new('-v', '-v', [CompletionResultType]::ParameterName, 'Use verbose output')
             [CompletionResult]::new('--verbose', '--verbose', [CompletionResultType]::ParameterName, 'Use verbose output')
-            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
-            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
             break
         }
         'rpk;help' {
@@ -267,9 +360,12 @@ Register-ArgumentCompleter -Native -CommandName 'rpk' -ScriptBlock {
             [CompletionResult]::new('list', 'list', [CompletionResultType]::ParameterValue, 'List all installed packages')
             [CompletionResult]::new('sync', 'sync', [CompletionResultType]::ParameterValue, 'Install any missing packages, re-generating the lock file')
             [CompletionResult]::new('add', 'add', [CompletionResultType]::ParameterValue, 'Add a new plugin to the config file')
+            [CompletionResult]::new('remove', 'remove', [CompletionResultType]::ParameterValue, 'Remove packages from the config file and uninstall them')
             [CompletionResult]::new('restore', 'restore', [CompletionResultType]::ParameterValue, 'Restore packages to the state in the lockfile')
             [CompletionResult]::new('update', 'update', [CompletionResultType]::ParameterValue, 'Update packages and re-generate the lock file')
+            [CompletionResult]::new('outdated', 'outdated', [CompletionResultType]::ParameterValue, 'List packages which have a different version available, without installing anything')
             [CompletionResult]::new('find', 'find', [CompletionResultType]::ParameterValue, 'Find packages matching the given query')
+            [CompletionResult]::new('use', 'use', [CompletionResultType]::ParameterValue, 'Switch a package to a version kept on disk, without network access')
             [CompletionResult]::new('cleanup', 'cleanup', [CompletionResultType]::ParameterValue, 'Remove packages which are not listed in the lock file')
             [CompletionResult]::new('env', 'env', [CompletionResultType]::ParameterValue, 'Prints the environment variables for rpk')
             [CompletionResult]::new('completions', 'completions', [CompletionResultType]::ParameterValue, 'Generate completions for the given shell')
@@ -289,15 +385,24 @@ Register-ArgumentCompleter -Native -CommandName 'rpk' -ScriptBlock {
         'rpk;help;add' {
             break
         }
+        'rpk;help;remove' {
+            break
+        }
         'rpk;help;restore' {
             break
         }
         'rpk;help;update' {
             break
         }
+        'rpk;help;outdated' {
+            break
+        }
         'rpk;help;find' {
             break
         }
+        'rpk;help;use' {
+            break
+        }
         'rpk;help;cleanup' {
             break
         }
@@ -4,19 +4,23 @@ use std::{
     path::PathBuf,
 };
 
-use clap::{ColorChoice, Parser};
+use clap::{ColorChoice, Parser, ValueEnum};
 use clap_complete::Shell;
 use url::Url;
 
 use crate::{
     context::{Output, Verbosity},
-    util,
+    util::{self, target::Target},
 };
 
 pub const ENV_CONFIG_DIR: &str = "RPK_CONFIG_DIR";
 pub const ENV_DATA_DIR: &str = "RPK_DATA_DIR";
 pub const ENV_CACHE_DIR: &str = "RPK_CACHE_DIR";
 pub const ENV_BIN_DIR: &str = "RPK_BIN_DIR";
+pub const ENV_SHARE_DIR: &str = "RPK_SHARE_DIR";
+pub const ENV_MAX_EXTRACT_SIZE: &str = "RPK_MAX_EXTRACT_SIZE";
+pub const ENV_MAX_EXTRACT_FILES: &str = "RPK_MAX_EXTRACT_FILES";
+pub const ENV_TARGET: &str = "RPK_TARGET";
 
 /// Resolved command line options.
 #[derive(Debug, PartialEq, Eq, Parser)]
@@ -52,6 +56,29 @@ pub struct Opt {
     #[clap(long, value_name = "PATH", env = ENV_BIN_DIR)]
     pub bin_dir: Option<PathBuf>,
 
+    /// The directory installed man pages and shell completions linked to.
+    #[clap(long, value_name = "PATH", env = ENV_SHARE_DIR)]
+    pub share_dir: Option<PathBuf>,
+
+    /// The maximum total size of the files extracted from an archive.
+    ///
+    /// Defaults to `4G`.
+    #[clap(long, value_name = "SIZE", env = ENV_MAX_EXTRACT_SIZE)]
+    #[arg(value_parser = size_parser)]
+    pub max_extract_size: Option<u64>,
+
+    /// The maximum number of the entries extracted from an archive.
+    ///
+    /// Defaults to `100000`.
+    #[clap(long, value_name = "NUM", env = ENV_MAX_EXTRACT_FILES)]
+    pub max_extract_files: Option<u64>,
+
+    /// The platform to resolve assets for instead of the current one, without installing them.
+    ///
+    /// Example: `linux-aarch64` or `linux-x86_64-musl`
+    #[clap(long, value_name = "TARGET", env = ENV_TARGET, global = true)]
+    pub target: Option<Target>,
+
     /// The subcommand to run.
     #[clap(subcommand)]
     pub command: SubCommand,
@@ -69,7 +96,11 @@ pub enum SubCommand {
 
     /// List all installed packages.
     #[clap(visible_aliases = ["l", "ls"])]
-    List,
+    List {
+        /// List all versions kept on disk instead of the locked ones.
+        #[clap(long)]
+        all_versions: bool,
+    },
 
     /// Install any missing packages, re-generating the lock file.
     #[clap(visible_alias = "s")]
@@ -78,13 +109,23 @@ pub enum SubCommand {
     /// Add a new plugin to the config file.
     #[clap(visible_alias = "a")]
     Add {
-        /// The github repository hosting the package
+        /// The repository hosting the package
         ///
         /// Example: `sharkdp/fd`
         #[clap(value_name = "REPO")]
         #[arg(value_parser = repo_parser)]
         repo: (String, String),
 
+        /// The source hosting the repository.
+        #[clap(long, value_name = "SOURCE", value_enum, default_value_t = SourceKind::Github)]
+        source: SourceKind,
+
+        /// The base URL of a self-managed instance of the source.
+        ///
+        /// Example: `https://github.example.com`
+        #[clap(long, value_name = "URL")]
+        host: Option<Url>,
+
         /// A unique name for the package. Defaults to the repo name.
         #[clap(long, value_name = "NAME")]
         name: Option<String>,
@@ -98,6 +139,18 @@ pub enum SubCommand {
         desc: Option<String>,
     },
 
+    /// Remove packages from the config file and uninstall them.
+    #[clap(visible_alias = "rm")]
+    Remove {
+        /// The packages to remove.
+        #[clap(value_name = "PKG", required = true)]
+        packages: Vec<String>,
+
+        /// Remove the cached assets of the packages as well.
+        #[clap(long)]
+        cache: bool,
+    },
+
     /// Restore packages to the state in the lockfile.
     #[clap(visible_alias = "r")]
     Restore {
@@ -114,6 +167,12 @@ pub enum SubCommand {
         package: Option<String>,
     },
 
+    /// List packages which have a different version available, without installing anything.
+    ///
+    /// Exits with code 2 when any package is outdated, or 1 on errors.
+    #[clap(visible_alias = "o")]
+    Outdated,
+
     /// Find packages matching the given query.
     #[clap(visible_aliases = ["f", "fd"])]
     Find {
@@ -126,11 +185,27 @@ pub enum SubCommand {
         top: u8,
     },
 
+    /// Switch a package to a version kept on disk, without network access.
+    ///
+    /// The version is locked until the package is updated.
+    Use {
+        /// The package and the installed version to switch to.
+        ///
+        /// Example: `fd@v10.1.0`
+        #[clap(value_name = "PKG@VERSION")]
+        #[arg(value_parser = pkg_version_parser)]
+        package: (String, String),
+    },
+
     /// Remove packages which are not listed in the lock file.
     Cleanup {
         /// Remove all cached data as well.
         #[clap(long)]
         cache: bool,
+
+        /// The number of the most recently installed versions of each package to keep besides the locked one.
+        #[clap(long, value_name = "NUM", default_value = "0")]
+        keep: usize,
     },
 
     /// Prints the environment variables for rpk.
@@ -157,6 +232,15 @@ pub enum SubCommand {
     Version,
 }
 
+/// The kind of source hosting a package.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SourceKind {
+    Github,
+    Gitlab,
+    /// Gitea and its derivatives like Forgejo and Codeberg.
+    Gitea,
+}
+
 impl Opt {
     pub fn color_enabled(&self) -> bool {
         let enabled = match self.color {
@@ -191,3 +275,29 @@ fn repo_parser(repo: &str) -> Result<(String, String), String> {
         None => Err("invalid repo format, should be: 'owner/repo'".into()),
     }
 }
+
+fn pkg_version_parser(package: &str) -> Result<(String, String), String> {
+    match package.split_once('@') {
+        Some((name, version)) if !name.is_empty() && !version.is_empty() => Ok((name.to_owned(), version.to_owned())),
+        _ => Err("invalid package format, should be: 'pkg@version'".into()),
+    }
+}
+
+fn size_parser(size: &str) -> Result<u64, String> {
+    let (num, unit) = match size.find(|c: char| !c.is_ascii_digit()) {
+        Some(i) => size.split_at(i),
+        None => (size, ""),
+    };
+    let shift = match unit.to_ascii_uppercase().trim_end_matches(['B', 'I']) {
+        "" => 0,
+        "K" => 10,
+        "M" => 20,
+        "G" => 30,
+        "T" => 40,
+        _ => return Err("invalid size unit, should be one of: K, M, G, T".into()),
+    };
+    num.parse::<u64>()
+        .ok()
+        .and_then(|num| num.checked_mul(1 << shift))
+        .ok_or_else(|| "invalid size, should be like: '512M'".into())
+}
@@ -1,4 +1,5 @@
 use std::{
+    collections::HashSet,
     fs,
     process,
     sync::{
@@ -24,8 +25,9 @@ use crate::{
     commands,
     config::{Config, EditableConfig, LockedConfig, Package, Source},
     context::Context,
+    installer::{find_links, installed_versions, uninstall_package, use_package},
     manager::{restore_package, restore_packages, sync_package, sync_packages},
-    provider::Github,
+    provider::{self, Github},
     util::{remove_file_if_exists, rm_rf, Emojify, Shorten as _},
 };
 
@@ -54,10 +56,14 @@ pub fn init(ctx: &Context, from: Option<Url>) -> Result<()> {
     sync(ctx)
 }
 
-pub fn list(ctx: &Context) -> Result<(), anyhow::Error> {
+pub fn list(ctx: &Context, all_versions: bool) -> Result<(), anyhow::Error> {
     let lcfg = LockedConfig::load(ctx)?;
     ctx.log_verbose_header("Loaded", ctx.lock_file.shorten()?);
 
+    if all_versions {
+        return list_versions(ctx, &lcfg);
+    }
+
     #[derive(Debug, Tabled)]
     #[tabled(rename_all = "UPPERCASE")]
     struct Item {
@@ -76,21 +82,106 @@ pub fn list(ctx: &Context) -> Result<(), anyhow::Error> {
             description: lpkg.desc.map(|s| s.emojify()).unwrap_or_default(),
         });
 
+    print_table(items);
+
+    Ok(())
+}
+
+/// List the versions of the packages kept on disk, the most recently installed first.
+fn list_versions(ctx: &Context, lcfg: &LockedConfig) -> Result<()> {
+    #[derive(Debug, Tabled)]
+    #[tabled(rename_all = "UPPERCASE")]
+    struct Item {
+        pkg:     String,
+        version: String,
+        locked:  String,
+    }
+
+    let mut items = Vec::new();
+    for entry in fs::read_dir(&ctx.data_dir)? {
+        let entry = entry?;
+        let Some(name) = entry.file_name().to_str().map(ToOwned::to_owned) else {
+            continue;
+        };
+        if !entry.file_type()?.is_dir() {
+            continue;
+        }
+        let locked = lcfg.pkgs.get(&name).map(|lpkg| lpkg.version.as_str());
+        for (version, _) in installed_versions(ctx, &name)? {
+            let locked = if locked == Some(version.as_str()) { "*" } else { "" };
+            items.push(Item { pkg: name.clone(), version, locked: locked.to_owned() });
+        }
+    }
+    items.sort_by(|a, b| a.pkg.cmp(&b.pkg));
+
+    print_table(items);
+
+    Ok(())
+}
+
+/// Report the packages which have a different version available, returns whether any exists.
+pub fn outdated(ctx: &Context) -> Result<bool> {
+    let cfg = Config::load(ctx)?;
+    ctx.log_verbose_header("Loaded", ctx.config_file.shorten()?);
+    let lcfg = LockedConfig::load(ctx)?;
+    ctx.log_verbose_header("Loaded", ctx.lock_file.shorten()?);
+
+    #[derive(Debug, Tabled)]
+    #[tabled(rename_all = "UPPERCASE")]
+    struct Item {
+        pkg:       String,
+        current:   String,
+        available: String,
+    }
+
+    let items: Vec<_> = cfg
+        .pkgs
+        .par_iter()
+        .map(|(name, pkg)| {
+            let available = provider::new(&pkg.source)?.resolve_version(pkg)?;
+            ctx.log_verbose_status("Fetched", format!("{name}@{available}"));
+            let current = lcfg.pkgs.get(name).map(|lpkg| lpkg.version.clone());
+            Ok((name, current, available))
+        })
+        .collect::<Result<Vec<_>>>()?
+        .into_iter()
+        .filter(|(_, current, available)| current.as_ref() != Some(available))
+        .map(|(name, current, available)| Item {
+            pkg: name.clone(),
+            current: current.unwrap_or_else(|| "-".to_owned()),
+            available,
+        })
+        .collect();
+
+    if items.is_empty() {
+        ctx.log_status("Checked", "All packages are up to date");
+        return Ok(false);
+    }
+
+    print_table(items);
+
+    Ok(true)
+}
+
+fn print_table<T: Tabled>(items: impl IntoIterator<Item = T>) {
     let mut table = Table::new(items);
     table
         .with(Style::empty())
         .modify(Rows::first(), Color::BOLD)
         .with(Padding::new(0, 4, 0, 0));
     println!("{table}");
-
-    Ok(())
 }
 
 pub fn add(ctx: &Context, mut pkg: Package) -> Result<()> {
+    let cfg = Config::load(ctx)?;
     let mut ecfg = EditableConfig::load(ctx)?;
     ctx.log_verbose_header("Loaded", ctx.config_file.shorten()?);
 
-    let lpkg = sync_package(ctx, &pkg, None, false)?;
+    // Only the package as given is saved, the default settings are applied when syncing.
+    let mut source = pkg.source.clone();
+    cfg.github.apply(&mut source);
+
+    let lpkg = sync_package(ctx, &Package { source, ..pkg.clone() }, None, false)?;
     pkg.desc = lpkg.desc.clone();
 
     ecfg.upsert(&pkg)?;
@@ -105,6 +196,48 @@ pub fn add(ctx: &Context, mut pkg: Package) -> Result<()> {
     Ok(())
 }
 
+pub fn remove(ctx: &Context, packages: Vec<String>, clear_cache: bool) -> Result<()> {
+    let mut ecfg = EditableConfig::load(ctx)?;
+    ctx.log_verbose_header("Loaded", ctx.config_file.shorten()?);
+    let mut lcfg = LockedConfig::load(ctx)?;
+    ctx.log_verbose_header("Loaded", ctx.lock_file.shorten()?);
+
+    // Remove all packages from the configs before touching any files.
+    let mut removed = Vec::new();
+    for name in packages {
+        let configured = ecfg.remove(&name)?;
+        let lpkg = lcfg.remove(&name);
+        if !configured && lpkg.is_none() {
+            bail!("package {} not found", name);
+        }
+        removed.push((name, lpkg));
+    }
+
+    for (name, lpkg) in removed {
+        uninstall_package(ctx, &name)?;
+
+        if let Some(lpkg) = lpkg.filter(|_| clear_cache) {
+            // the assets locked for other platforms are cached too
+            let filenames = [&lpkg.filename]
+                .into_iter()
+                .chain(lpkg.targets.values().map(|a| &a.filename));
+            for filename in filenames.filter(|filename| !filename.is_empty()).unique() {
+                let path = ctx.cache_dir.join(filename);
+                remove_file_if_exists(&path)?;
+                ctx.log_verbose_status("Removed", path.shorten()?);
+            }
+        }
+
+        ctx.log_status("Removed", &name);
+    }
+
+    ecfg.save()?;
+    lcfg.save()?;
+    ctx.log_verbose_header("Locked", ctx.lock_file.shorten()?);
+
+    Ok(())
+}
+
 pub fn sync(ctx: &Context) -> Result<(), anyhow::Error> {
     let cfg = Config::load(ctx)?;
     ctx.log_verbose_header("Loaded", ctx.config_file.shorten()?);
@@ -182,9 +315,34 @@ pub fn update(ctx: &Context, package: Option<String>) -> Result<(), anyhow::Erro
     Ok(())
 }
 
-pub fn cleanup(ctx: &Context, clear_cache: bool) -> Result<()> {
+pub fn use_version(ctx: &Context, name: &str, version: &str) -> Result<()> {
+    let mut lcfg = LockedConfig::load(ctx)?;
+    ctx.log_verbose_header("Loaded", ctx.lock_file.shorten()?);
+
+    let Some(old) = lcfg.pkgs.get(name).map(|lpkg| lpkg.version.clone()) else {
+        bail!("package {} not found", name);
+    };
+    let lpkg = use_package(ctx, name, version)?;
+    lcfg.upsert(lpkg);
+    ctx.log_status("Switched", format!("{name}@{old} => {version}"));
+
+    lcfg.save()?;
+    ctx.log_verbose_header("Locked", ctx.lock_file.shorten()?);
+
+    Ok(())
+}
+
+pub fn cleanup(ctx: &Context, clear_cache: bool, keep: usize) -> Result<()> {
     let lcfg = LockedConfig::load(ctx)?;
 
+    // the most recently installed versions besides the locked ones are kept
+    let mut kept = HashSet::new();
+    for (name, lpkg) in &lcfg.pkgs {
+        let versions = installed_versions(ctx, name)?.into_iter();
+        let versions = versions.filter(|(version, _)| *version != lpkg.version).take(keep);
+        kept.extend(versions.map(|(version, _)| ctx.data_dir.join(name).join(version)));
+    }
+
     for entry in WalkDir::new(&ctx.data_dir).max_depth(2) {
         let entry = entry?;
         match entry.depth() {
@@ -201,7 +359,7 @@ pub fn cleanup(ctx: &Context, clear_cache: bool) -> Result<()> {
                 let mut parts = entry.path().components().map(|c| c.as_os_str().to_str()).rev();
                 match (parts.next(), parts.next()) {
                     (Some(Some(version)), Some(Some(name))) => match lcfg.pkgs.get(name) {
-                        Some(lpkg) if lpkg.version == version => {
+                        Some(lpkg) if lpkg.version == version || kept.contains(entry.path()) => {
                             continue;
                         }
                         _ => {
@@ -219,6 +377,41 @@ pub fn cleanup(ctx: &Context, clear_cache: bool) -> Result<()> {
         }
     }
 
+    // remove the links to the files which are no longer locked
+    for (path, target) in find_links(ctx)? {
+        let rel = target.strip_prefix(&ctx.data_dir)?;
+        let locked = rel
+            .iter()
+            .next()
+            .and_then(|name| lcfg.pkgs.get(name.to_str()?))
+            .is_some_and(|lpkg| {
+                let Ok(file) = target.strip_prefix(ctx.data_dir.join(&lpkg.name).join(&lpkg.version)) else {
+                    return false;
+                };
+                match path.starts_with(&ctx.bin_dir) {
+                    true => path
+                        .file_name()
+                        .and_then(|name| name.to_str())
+                        .is_some_and(|name| lpkg.bin_names().contains(&name)),
+                    false => {
+                        // the files detected when restoring are not recorded, so the links into the locked version
+                        // are kept unless the files are listed
+                        let is_manpage = path
+                            .strip_prefix(&ctx.share_dir)
+                            .is_ok_and(|path| path.starts_with("man"));
+                        let files = if is_manpage { &lpkg.manpages } else { &lpkg.completions };
+                        files.as_ref().map_or(true, |files| {
+                            file.to_str().is_some_and(|file| files.iter().any(|f| f == file))
+                        })
+                    }
+                }
+            });
+        if !locked {
+            fs::remove_file(&path)?;
+            ctx.log_status("Unlinked", path.shorten()?);
+        }
+    }
+
     if clear_cache {
         for entry in fs::read_dir(&ctx.cache_dir)? {
             let entry = entry?;
@@ -231,7 +424,8 @@ pub fn cleanup(ctx: &Context, clear_cache: bool) -> Result<()> {
 }
 
 pub fn find(query: String, top: u8, ctx: &Context) -> Result<(), anyhow::Error> {
-    let gh = Github::new(ctx.clone())?;
+    let cfg = Config::load(ctx)?;
+    let gh = Github::new(cfg.github.host.as_ref(), cfg.github.api.as_ref())?;
     let repos = gh.search_repo(&query, top)?;
 
     let stars_width = Arc::new(AtomicUsize::new(0));
@@ -273,12 +467,13 @@ pub fn find(query: String, top: u8, ctx: &Context) -> Result<(), anyhow::Error>
 
     let pkg = Package {
         name,
-        source: Source::Github { repo: answer.fullname },
+        source: Source::Github { repo: answer.fullname, host: None, api: None },
         version: None,
         desc: match answer.desc.is_empty() {
             false => Some(answer.desc.emojify()),
             true => None,
         },
+        ..Default::default()
     };
 
     debug!("selected: {:?}", pkg);
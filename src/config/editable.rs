@@ -64,4 +64,97 @@ impl EditableConfig {
 
         Ok(())
     }
+
+    /// Remove a package from the config, returns whether the package exists.
+    pub fn remove(&mut self, name: &str) -> Result<bool> {
+        let pkgs = match self.doc.get_mut("pkgs") {
+            None => return Ok(false),
+            Some(toml_edit::Item::Table(pkgs)) => pkgs,
+            Some(_) => bail!("current `pkgs` entry is not a table"),
+        };
+
+        let table = match pkgs.remove(name) {
+            Some(toml_edit::Item::Table(table)) => table,
+            // the comments of inline tables are decorating their keys, which are removed along
+            Some(toml_edit::Item::Value(toml_edit::Value::InlineTable(_))) => return Ok(true),
+            Some(_) => bail!("package `{name}` is not a table"),
+            None => return Ok(false),
+        };
+
+        // Comments separated from the package by a blank line (e.g. the file header) are not owned by the package,
+        // move them to the next table.
+        let prefix = table
+            .decor()
+            .prefix()
+            .and_then(|prefix| prefix.as_str())
+            .unwrap_or_default();
+        if let Some(detached) = prefix.rfind("\n\n").map(|i| &prefix[..i]) {
+            let next = pkgs
+                .iter_mut()
+                .filter_map(|(_, item)| item.as_table_mut())
+                .filter(|next| next.position() > table.position())
+                .min_by_key(|next| next.position());
+            match next {
+                Some(next) => {
+                    let prefix = next
+                        .decor()
+                        .prefix()
+                        .and_then(|prefix| prefix.as_str())
+                        .unwrap_or_default();
+                    let prefix = format!("{detached}\n\n{}", prefix.trim_start_matches('\n'));
+                    next.decor_mut().set_prefix(prefix);
+                }
+                None => {
+                    let trailing = format!("{detached}\n{}", self.doc.trailing().as_str().unwrap_or_default());
+                    self.doc.set_trailing(trailing);
+                }
+            }
+        }
+
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn remove(config: &str, name: &str) -> (bool, String) {
+        let mut ecfg = EditableConfig { ctx: Context::default(), doc: config.parse().unwrap() };
+        let removed = ecfg.remove(name).unwrap();
+        (removed, ecfg.doc.to_string())
+    }
+
+    #[test]
+    fn remove_table() {
+        let config = "# header\n\n# fd\n[pkgs.fd]\nrepo = \"sharkdp/fd\"\n\n[pkgs.rg]\nrepo = \"BurntSushi/ripgrep\"\n";
+        let (removed, config) = remove(config, "fd");
+        assert!(removed);
+        assert_eq!(config, "# header\n\n[pkgs.rg]\nrepo = \"BurntSushi/ripgrep\"\n");
+    }
+
+    #[test]
+    fn remove_inline_table() {
+        let config = "[pkgs]\nfd = { repo = \"sharkdp/fd\" }\nrg = { repo = \"BurntSushi/ripgrep\" }\n";
+        let (removed, config) = remove(config, "fd");
+        assert!(removed);
+        assert_eq!(config, "[pkgs]\nrg = { repo = \"BurntSushi/ripgrep\" }\n");
+    }
+
+    #[test]
+    fn remove_missing() {
+        let config = "[pkgs.fd]\nrepo = \"sharkdp/fd\"\n";
+        assert_eq!(remove(config, "rg"), (false, config.to_owned()));
+        assert_eq!(remove("", "rg"), (false, String::new()));
+    }
+
+    #[test]
+    fn remove_invalid() {
+        let mut ecfg = EditableConfig {
+            ctx: Context::default(),
+            doc: "[pkgs]\nfd = 1\n".parse().unwrap(),
+        };
+        let err = ecfg.remove("fd").unwrap_err();
+        assert_eq!(err.to_string(), "package `fd` is not a table");
+    }
 }
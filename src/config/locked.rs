@@ -6,7 +6,7 @@ use url::Url;
 
 use crate::{
     context::Context,
-    util::{load_toml, not_found_err},
+    util::{load_toml, not_found_err, target::Target},
 };
 
 use super::Source;
@@ -26,13 +26,52 @@ pub struct LockedConfig {
 #[serde(rename_all = "lowercase")]
 pub struct LockedPackage {
     #[serde(skip)]
-    pub name:         String,
-    pub version:      String,
+    pub name:             String,
+    pub version:          String,
     #[serde(flatten)]
-    pub source:       Source,
-    pub desc:         Option<String>,
+    pub source:           Source,
+    pub desc:             Option<String>,
+    /// The asset for the current platform, filled from `targets` if it's locked per platform.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub filename:         String,
+    pub download_url:     Option<Url>,
+    /// The SHA-256 digest of the downloaded asset.
+    pub sha256:           Option<String>,
+    /// The linked binaries, mapped from the link names to their paths relative to the install directory.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub bins:             BTreeMap<String, String>,
+    /// The linked shell completions, relative to the install directory.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub completions:      Option<Vec<String>>,
+    /// The linked man pages, relative to the install directory.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub manpages:         Option<Vec<String>>,
+    /// Whether the AppImage is extracted.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub extract_appimage: bool,
+    /// The assets resolved for each of the configured platforms.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub targets:          BTreeMap<Target, LockedAsset>,
+}
+
+/// The asset of a package resolved for a platform.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct LockedAsset {
     pub filename:     String,
     pub download_url: Option<Url>,
+    /// The SHA-256 digest of the downloaded asset.
+    pub sha256:       Option<String>,
+}
+
+impl LockedPackage {
+    /// The names of the links owned by the package in the bin directory.
+    pub fn bin_names(&self) -> Vec<&str> {
+        match self.bins.is_empty() {
+            // the binary named after the package is linked by default
+            true => vec![self.name.as_str()],
+            false => self.bins.keys().map(String::as_str).collect(),
+        }
+    }
 }
 
 impl LockedConfig {
@@ -48,16 +87,33 @@ impl LockedConfig {
 
         lcfg.ctx = ctx.clone();
 
-        // Set the package names for convenience.
+        // Set the package names for convenience, and pick the assets of the target platform.
         for (name, lpkg) in lcfg.pkgs.iter_mut() {
             lpkg.name = name.clone();
+            let asset = lpkg.targets.get(&ctx.target).or_else(|| {
+                lpkg.targets
+                    .iter()
+                    .find_map(|(target, asset)| target.matches(&ctx.target).then_some(asset))
+            });
+            if let Some(asset) = asset {
+                lpkg.filename.clone_from(&asset.filename);
+                lpkg.download_url.clone_from(&asset.download_url);
+                lpkg.sha256.clone_from(&asset.sha256);
+            }
         }
         Ok(lcfg)
     }
 
     /// Write this `LockedConfig` to the given path.
     pub fn save(&self) -> Result<()> {
-        let buf = toml::to_string_pretty(self).context("failed to serialize `LockedConfig`")?;
+        // the assets locked per platform are kept in `targets` only, so that the file is the same on every platform
+        let mut lcfg = LockedConfig::new(self.ctx.clone(), self.pkgs.clone());
+        for lpkg in lcfg.pkgs.values_mut().filter(|lpkg| !lpkg.targets.is_empty()) {
+            lpkg.filename.clear();
+            lpkg.download_url = None;
+            lpkg.sha256 = None;
+        }
+        let buf = toml::to_string_pretty(&lcfg).context("failed to serialize `LockedConfig`")?;
         fs::write(&self.ctx.lock_file, buf).with_context(|| format!("failed to save {}", self.ctx.lock_file.display()))
     }
 
@@ -65,4 +121,9 @@ impl LockedConfig {
     pub fn upsert(&mut self, lpkg: LockedPackage) {
         self.pkgs.insert(lpkg.name.clone(), lpkg);
     }
+
+    /// Remove a package from the configuration, returns the removed package if it exists.
+    pub fn remove(&mut self, name: &str) -> Option<LockedPackage> {
+        self.pkgs.remove(name)
+    }
 }
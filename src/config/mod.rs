@@ -2,7 +2,7 @@ mod editable;
 mod locked;
 
 pub use editable::EditableConfig;
-pub use locked::{LockedConfig, LockedPackage};
+pub use locked::{LockedAsset, LockedConfig, LockedPackage};
 
 use std::{collections::BTreeMap, fmt, fs, str};
 
@@ -14,41 +14,233 @@ use serde::{
     Serialize,
 };
 
+use url::Url;
+
 use crate::{
     context::Context,
-    util::{load_toml, not_found_err, remove_file_if_exists},
+    util::{
+        load_toml,
+        not_found_err,
+        remove_file_if_exists,
+        target::{Libc, Target},
+    },
 };
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "snake_case")]
 pub struct Config {
+    /// The default settings of github sources.
+    #[serde(default, skip_serializing_if = "GithubConfig::is_empty")]
+    pub github: GithubConfig,
+
+    /// The platform to resolve the assets for, unless given on the command line.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<Target>,
+
+    /// The default platforms to lock the assets of packages for, besides the current one.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub targets: Vec<Target>,
+
+    /// The default libc to prefer the assets of on linux.
+    #[serde(default, skip_serializing_if = "LibcPreference::is_auto")]
+    pub libc: LibcPreference,
+
     #[serde(default)]
     pub pkgs: BTreeMap<String, Package>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct GithubConfig {
+    /// The web host of a GitHub Enterprise Server.
+    pub host: Option<Url>,
+    /// The API base URL of a GitHub Enterprise Server.
+    pub api:  Option<Url>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub struct Package {
     #[serde(skip)]
-    pub name:    String,
-    pub version: Option<String>,
+    pub name:             String,
+    pub version:          Option<String>,
     #[serde(flatten)]
-    pub source:  Source,
-    pub desc:    Option<String>,
+    pub source:           Source,
+    pub desc:             Option<String>,
+    /// The public key to verify the signature of the downloaded asset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verify:           Option<Verify>,
+    /// The release channel to follow when no exact version is specified.
+    #[serde(default, skip_serializing_if = "Channel::is_stable")]
+    pub channel:          Channel,
+    /// The glob pattern of the release tags to follow, e.g. `nightly-*`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag:              Option<String>,
+    /// The pattern of the asset to install, which bypasses the default asset selection.
+    ///
+    /// Example: `asset = "tool_{os}_*.tar.gz"`, or a regex like `asset = "/tool_.*_64bit/"`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub asset:            Option<String>,
+    /// The patterns of the assets to exclude from the selection.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude:          Vec<String>,
+    /// The binaries to link, defaults to the one named after the package.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bins:             Option<Bins>,
+    /// The paths of the shell completions in the archive, detected by default.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub completions:      Option<Vec<String>>,
+    /// The paths of the man pages in the archive, detected by default.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub manpages:         Option<Vec<String>>,
+    /// Extract the AppImage instead of running it as is, for systems without FUSE.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub extract_appimage: bool,
+    /// The platforms to lock the assets for, besides the current one.
+    ///
+    /// Example: `targets = ["linux-x86_64", "macos-aarch64"]`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub targets:          Vec<Target>,
+    /// The libc to prefer the assets of on linux, unless the target specifies one.
+    #[serde(default, skip_serializing_if = "LibcPreference::is_auto")]
+    pub libc:             LibcPreference,
+}
+
+/// The binaries of a package to link into the bin directory.
+///
+/// Example: `bins = ["rga", "rga-preproc"]` or `bins = { rg = "bin/ripgrep" }`
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum Bins {
+    /// The names of the binaries, which are linked under the same names.
+    List(Vec<String>),
+    /// The link names mapped to the names or paths of the binaries in the archive.
+    Map(BTreeMap<String, String>),
+}
+
+impl Bins {
+    /// The pairs of the link name and the name or path of the binary in the archive.
+    pub fn entries(&self) -> Vec<(&str, &str)> {
+        match self {
+            Bins::List(names) => names.iter().map(|name| (name.as_str(), name.as_str())).collect(),
+            Bins::Map(map) => map.iter().map(|(dst, src)| (dst.as_str(), src.as_str())).collect(),
+        }
+    }
+}
+
+/// The release channel of a package.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Channel {
+    /// The latest release which is neither a draft nor a prerelease.
+    #[default]
+    Stable,
+    /// The latest release including prereleases.
+    Prerelease,
+    /// The latest nightly build, whose tag matches `nightly*` unless a tag pattern is given.
+    ///
+    /// Nightly builds are usually republished under the same tag, so the asset is always downloaded again on update.
+    Nightly,
+}
+
+impl Channel {
+    fn is_stable(&self) -> bool {
+        *self == Channel::Stable
+    }
+}
+
+/// The libc to prefer the assets of on linux.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LibcPreference {
+    /// The libc detected on the current platform, or musl for other platforms and the assets locked for them, since its
+    /// binaries are usually static.
+    #[default]
+    Auto,
+    Gnu,
+    Musl,
+}
+
+impl LibcPreference {
+    fn is_auto(&self) -> bool {
+        *self == LibcPreference::Auto
+    }
+
+    /// Resolve the libc to select the assets of for the target platform, `None` if there's no preference.
+    pub fn resolve(self, target: &Target) -> Option<Libc> {
+        match self {
+            _ if target.os != "linux" => None,
+            _ if target.libc.is_some() => target.libc,
+            LibcPreference::Gnu => Some(Libc::Gnu),
+            LibcPreference::Musl => Some(Libc::Musl),
+            LibcPreference::Auto => None,
+        }
+    }
+}
+
+/// The public key to verify the signature of an asset.
+///
+/// Example: `verify = { minisign = "RWQf6LRCGA9i53mlYecO4IzT51TGPpvWucNSCh1CBM0QTaLn73Y7GFO3" }`
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Verify {
+    /// A minisign public key, verified against the `.minisig` asset.
+    Minisign(String),
+    /// A base64 encoded ed25519 public key, verified against the `.sig` asset.
+    Ed25519(String),
+    /// A PEM encoded ECDSA P-256 public key generated by cosign, verified against the `.sig` asset.
+    Cosign(String),
 }
 
 #[derive(Debug, Serialize, Clone, PartialEq, Eq)]
 #[serde(tag = "source")]
 #[serde(rename_all = "snake_case")]
 pub enum Source {
-    Github { repo: String },
+    Github {
+        repo: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        host: Option<Url>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        api:  Option<Url>,
+    },
+    Gitlab {
+        repo: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        host: Option<Url>,
+    },
+    Gitea {
+        repo: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        host: Option<Url>,
+    },
+    /// A direct download URL, which is a template expanded with `{version}`, `{os}`, `{arch}` and `{target}`.
+    Url { url: String },
+}
+
+/// GitHub is the source when not specified.
+impl Default for Source {
+    fn default() -> Self {
+        Self::Github { repo: String::new(), host: None, api: None }
+    }
 }
 
 impl fmt::Display for Source {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::Github { repo } => {
-                write!(f, "github.com:{}", repo)
+            Self::Github { repo, host, .. } => {
+                let host = host.as_ref().and_then(Url::host_str).unwrap_or("github.com");
+                write!(f, "{}:{}", host, repo)
+            }
+            Self::Gitlab { repo, host } => {
+                let host = host.as_ref().and_then(Url::host_str).unwrap_or("gitlab.com");
+                write!(f, "{}:{}", host, repo)
+            }
+            Self::Gitea { repo, host } => {
+                let host = host.as_ref().and_then(Url::host_str).unwrap_or("codeberg.org");
+                write!(f, "{}:{}", host, repo)
+            }
+            Self::Url { url } => {
+                write!(f, "{}", url)
             }
         }
     }
@@ -58,11 +250,19 @@ impl Source {
     fn is_default(&self) -> bool {
         matches!(self, Self::Github { .. })
     }
+
+    /// The repository hosting the package, if any.
+    pub fn repo(&self) -> Option<&str> {
+        match self {
+            Self::Github { repo, .. } | Self::Gitlab { repo, .. } | Self::Gitea { repo, .. } => Some(repo),
+            Self::Url { .. } => None,
+        }
+    }
 }
 
 impl fmt::Display for Package {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let Self { name, version, source, desc: _ } = self;
+        let Self { name, version, source, .. } = self;
         write!(
             f,
             "{name}@{version} from {source}",
@@ -96,10 +296,13 @@ impl<'de> Visitor<'de> for SourceVisitor {
         enum Field {
             Source,
             Repo,
+            Host,
+            Api,
+            Url,
         }
-        const FIELDS: &[&str] = &["github"];
+        const FIELDS: &[&str] = &["github", "gitlab", "gitea", "url"];
 
-        let (mut source, mut repo) = (None, None);
+        let (mut source, mut repo, mut host, mut api, mut url) = (None, None, None, None, None);
         while let Some(key) = map.next_key()? {
             match key {
                 Field::Source => match source {
@@ -110,11 +313,61 @@ impl<'de> Visitor<'de> for SourceVisitor {
                     None => repo = Some(map.next_value()?),
                     Some(_) => return Err(Error::duplicate_field("repo")),
                 },
+                Field::Host => match host {
+                    None => host = Some(map.next_value()?),
+                    Some(_) => return Err(Error::duplicate_field("host")),
+                },
+                Field::Api => match api {
+                    None => api = Some(map.next_value()?),
+                    Some(_) => return Err(Error::duplicate_field("api")),
+                },
+                Field::Url => match url {
+                    None => url = Some(map.next_value()?),
+                    Some(_) => return Err(Error::duplicate_field("url")),
+                },
             }
         }
 
+        // reject the fields which are not supported by the given source
+        macro_rules! deny {
+            ($source:expr, $($field:ident),+) => {
+                $(if $field.is_some() {
+                    return Err(Error::custom(format!(
+                        "`{}` is not supported by {} source",
+                        stringify!($field),
+                        $source
+                    )));
+                })+
+            };
+        }
+
         let source = match source.unwrap_or("github".to_owned()).as_str() {
-            "github" => Source::Github { repo: repo.ok_or_else(|| Error::missing_field("repo"))? },
+            "github" => {
+                deny!("github", url);
+                Source::Github {
+                    repo: repo.ok_or_else(|| Error::missing_field("repo"))?,
+                    host,
+                    api,
+                }
+            }
+            "gitlab" => {
+                deny!("gitlab", api, url);
+                Source::Gitlab {
+                    repo: repo.ok_or_else(|| Error::missing_field("repo"))?,
+                    host,
+                }
+            }
+            "gitea" => {
+                deny!("gitea", api, url);
+                Source::Gitea {
+                    repo: repo.ok_or_else(|| Error::missing_field("repo"))?,
+                    host,
+                }
+            }
+            "url" => {
+                deny!("url", repo, host, api);
+                Source::Url { url: url.ok_or_else(|| Error::missing_field("url"))? }
+            }
             s => return Err(Error::unknown_variant(s, FIELDS)),
         };
 
@@ -130,9 +383,16 @@ impl Config {
             cfg => cfg.with_context(|| format!("failed to load {}", ctx.config_file.display()))?,
         };
 
-        // Set the package names for convenience.
+        // Set the package names for convenience and apply the default settings.
         for (name, pkg) in cfg.pkgs.iter_mut() {
             pkg.name = name.clone();
+            cfg.github.apply(&mut pkg.source);
+            if pkg.targets.is_empty() {
+                pkg.targets.clone_from(&cfg.targets);
+            }
+            if pkg.libc.is_auto() {
+                pkg.libc = cfg.libc;
+            }
         }
 
         Ok(cfg)
@@ -149,13 +409,169 @@ impl Config {
     }
 }
 
+impl GithubConfig {
+    fn is_empty(&self) -> bool {
+        self.host.is_none() && self.api.is_none()
+    }
+
+    /// Apply the settings to the source if it's a github source without its own settings.
+    pub fn apply(&self, source: &mut Source) {
+        if let Source::Github { host: host @ None, api: api @ None, .. } = source {
+            host.clone_from(&self.host);
+            api.clone_from(&self.api);
+        }
+    }
+}
+
+impl Package {
+    /// The platforms to lock the assets for along with the target one, none if only the current platform is needed.
+    pub fn locked_targets(&self, target: &Target) -> Vec<Target> {
+        if self.targets.is_empty() && target.is_host() {
+            return Vec::new();
+        }
+        let mut targets: Vec<_> = self.targets.iter().chain([target]).cloned().collect();
+        targets.sort();
+        targets.dedup();
+        targets
+    }
+}
+
 impl From<LockedPackage> for Package {
     fn from(val: LockedPackage) -> Self {
         Package {
-            name:    val.name,
+            name: val.name,
             version: val.version.into(),
-            source:  val.source,
-            desc:    val.desc,
+            source: val.source,
+            desc: val.desc,
+            bins: (!val.bins.is_empty()).then_some(Bins::Map(val.bins)),
+            completions: val.completions,
+            manpages: val.manpages,
+            extract_appimage: val.extract_appimage,
+            targets: val.targets.into_keys().collect(),
+            ..Default::default()
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source(toml: &str) -> Result<Source, toml::de::Error> {
+        toml::from_str::<Package>(toml).map(|pkg| pkg.source)
+    }
+
+    fn url(s: &str) -> Option<Url> {
+        Some(s.parse().unwrap())
+    }
+
+    #[test]
+    fn parse_sources() {
+        assert_eq!(source(r#"repo = "owner/tool""#).unwrap(), Source::Github {
+            repo: "owner/tool".into(),
+            host: None,
+            api:  None,
+        });
+        let toml = r#"
+            source = "github"
+            repo = "owner/tool"
+            host = "https://git.example.com"
+            api = "https://git.example.com/api/v3"
+        "#;
+        assert_eq!(source(toml).unwrap(), Source::Github {
+            repo: "owner/tool".into(),
+            host: url("https://git.example.com"),
+            api:  url("https://git.example.com/api/v3"),
+        });
+        assert_eq!(
+            source("source = \"gitlab\"\nrepo = \"owner/tool\"").unwrap(),
+            Source::Gitlab { repo: "owner/tool".into(), host: None }
+        );
+        let toml = "source = \"gitea\"\nrepo = \"owner/tool\"\nhost = \"https://gitea.example.com\"";
+        assert_eq!(source(toml).unwrap(), Source::Gitea {
+            repo: "owner/tool".into(),
+            host: url("https://gitea.example.com"),
+        });
+        let toml = "source = \"url\"\nurl = \"https://example.com/tool-{version}.tar.gz\"";
+        assert_eq!(source(toml).unwrap(), Source::Url {
+            url: "https://example.com/tool-{version}.tar.gz".into(),
+        });
+    }
+
+    #[test]
+    fn reject_conflicting_source_fields() {
+        let err = |toml: &str| source(toml).unwrap_err().message().to_owned();
+        assert_eq!(
+            err("repo = \"owner/tool\"\nurl = \"https://example.com\""),
+            "`url` is not supported by github source"
+        );
+        assert_eq!(
+            err("source = \"gitlab\"\nrepo = \"owner/tool\"\napi = \"https://gitlab.com/api\""),
+            "`api` is not supported by gitlab source"
+        );
+        assert_eq!(
+            err("source = \"gitea\"\nrepo = \"owner/tool\"\nurl = \"https://example.com\""),
+            "`url` is not supported by gitea source"
+        );
+        assert_eq!(
+            err("source = \"url\"\nurl = \"https://example.com\"\nrepo = \"owner/tool\""),
+            "`repo` is not supported by url source"
+        );
+        assert_eq!(
+            err("source = \"url\"\nurl = \"https://example.com\"\nhost = \"https://example.com\""),
+            "`host` is not supported by url source"
+        );
+        assert_eq!(err("source = \"gitlab\""), "missing field `repo`");
+        assert_eq!(err("source = \"url\""), "missing field `url`");
+        assert!(err("source = \"bitbucket\"\nrepo = \"owner/tool\"").starts_with("unknown variant `bitbucket`"));
+    }
+
+    #[test]
+    fn default_hosts() {
+        let display = |toml: &str| source(toml).unwrap().to_string();
+        assert_eq!(display(r#"repo = "owner/tool""#), "github.com:owner/tool");
+        assert_eq!(
+            display("source = \"gitlab\"\nrepo = \"owner/tool\""),
+            "gitlab.com:owner/tool"
+        );
+        assert_eq!(
+            display("source = \"gitea\"\nrepo = \"owner/tool\""),
+            "codeberg.org:owner/tool"
+        );
+        assert_eq!(
+            display("repo = \"owner/tool\"\nhost = \"https://git.example.com\""),
+            "git.example.com:owner/tool"
+        );
+    }
+
+    #[test]
+    fn apply_github_config() {
+        let config = GithubConfig {
+            host: url("https://git.example.com"),
+            api:  url("https://git.example.com/api/v3"),
+        };
+
+        let mut github = Source::Github { repo: "owner/tool".into(), host: None, api: None };
+        config.apply(&mut github);
+        assert_eq!(github, Source::Github {
+            repo: "owner/tool".into(),
+            host: config.host.clone(),
+            api:  config.api.clone(),
+        });
+
+        // the settings of the package itself take precedence
+        let own = Source::Github {
+            repo: "owner/tool".into(),
+            host: url("https://other.example.com"),
+            api:  None,
+        };
+        let mut source = own.clone();
+        config.apply(&mut source);
+        assert_eq!(source, own);
+
+        let gitlab = Source::Gitlab { repo: "owner/tool".into(), host: None };
+        let mut source = gitlab.clone();
+        config.apply(&mut source);
+        assert_eq!(source, gitlab);
+    }
+}
@@ -7,6 +7,8 @@ use serde::{Deserialize, Serialize};
 pub use yansi::Color;
 use yansi::Paint;
 
+use crate::util::target::Target;
+
 #[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
 pub struct Context {
     /// The version of the application.
@@ -27,12 +29,24 @@ pub struct Context {
     /// The location of the binary directory.
     pub bin_dir: PathBuf,
 
+    /// The location of the directory for man pages and shell completions.
+    #[serde(default)]
+    pub share_dir: PathBuf,
+
     /// The location of the lock file.
     #[serde(skip)]
     pub lock_file: PathBuf,
 
     #[serde(skip)]
     pub output: Output,
+
+    /// The limits of extracting archives.
+    #[serde(skip)]
+    pub limits: Limits,
+
+    /// The platform to resolve assets for, packages are only installed if it's the current one.
+    #[serde(skip)]
+    pub target: Target,
 }
 
 /// The output style.
@@ -44,6 +58,21 @@ pub struct Output {
     pub no_color:  bool,
 }
 
+/// The limits of extracting an archive, guarding against archive bombs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Limits {
+    /// The maximum total size of the extracted files in bytes.
+    pub size:  u64,
+    /// The maximum number of the extracted entries.
+    pub files: u64,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self { size: 4 << 30, files: 100_000 }
+    }
+}
+
 /// The requested verbosity of output.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd)]
 pub enum Verbosity {
@@ -0,0 +1,788 @@
+//! Extraction of archives, guarding against path traversal and archive bombs.
+
+use std::{
+    collections::VecDeque,
+    fs,
+    io::{self, BufRead as _, Read, Seek},
+    os::unix::fs::OpenOptionsExt as _,
+    path::{Component, Path, PathBuf},
+};
+
+use anyhow::{anyhow, bail, Result};
+use bzip2::read::BzDecoder;
+use flate2::read::GzDecoder;
+use tar::{Archive as TarArchive, EntryType};
+use tracing::trace;
+use walkdir::WalkDir;
+use xz2::read::XzDecoder;
+use zip::ZipArchive;
+use zstd::stream::read::Decoder as ZstdDecoder;
+
+use crate::context::Limits;
+
+/// The maximum number of symlinks followed when resolving a path, the same as linux.
+const MAX_SYMLINK_HOPS: usize = 40;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    Gzip,
+    Xz,
+    Bzip2,
+    Zstd,
+}
+
+impl Compression {
+    pub(super) fn from_extension(ext: &str) -> Option<Self> {
+        match ext {
+            "gz" => Some(Compression::Gzip),
+            "xz" => Some(Compression::Xz),
+            "bz2" => Some(Compression::Bzip2),
+            "zst" => Some(Compression::Zstd),
+            _ => None,
+        }
+    }
+
+    /// Wrap the reader with the decoder of the compression.
+    pub(super) fn decoder<'a>(compression: Option<Self>, reader: impl Read + 'a) -> io::Result<Box<dyn Read + 'a>> {
+        Ok(match compression {
+            Some(Compression::Gzip) => Box::new(GzDecoder::new(reader)),
+            Some(Compression::Xz) => Box::new(XzDecoder::new(reader)),
+            Some(Compression::Bzip2) => Box::new(BzDecoder::new(reader)),
+            Some(Compression::Zstd) => Box::new(ZstdDecoder::new(reader)?),
+            None => Box::new(reader),
+        })
+    }
+
+    /// Detect the compression from the magic bytes.
+    fn from_magic(magic: &[u8]) -> Option<Self> {
+        match magic {
+            [0x1f, 0x8b, ..] => Some(Compression::Gzip),
+            [0xfd, b'7', b'z', b'X', b'Z', ..] => Some(Compression::Xz),
+            [b'B', b'Z', b'h', ..] => Some(Compression::Bzip2),
+            [0x28, 0xb5, 0x2f, 0xfd, ..] => Some(Compression::Zstd),
+            _ => None,
+        }
+    }
+}
+
+/// Extracts the entries of archives into a directory.
+///
+/// Entries with absolute paths or `..` components, symlinks pointing outside of the directory and entries written
+/// through symlinks are refused, setuid and setgid bits are stripped, and the total size and number of the entries are
+/// limited.
+pub(super) struct Extractor<'a> {
+    dir:    &'a Path,
+    limits: Limits,
+    size:   u64,
+    files:  u64,
+}
+
+impl<'a> Extractor<'a> {
+    pub fn new(dir: &'a Path, limits: Limits) -> Self {
+        Self { dir, limits, size: 0, files: 0 }
+    }
+
+    /// Extract a single file, which is the binary itself.
+    pub fn unpack_file(&mut self, name: &str, reader: impl Read) -> Result<()> {
+        self.file(Path::new(name), 0o755, reader)
+    }
+
+    pub fn unpack_tar(&mut self, reader: impl Read) -> Result<()> {
+        let mut archive = TarArchive::new(reader);
+        for entry in archive.entries()? {
+            let mut entry = entry?;
+            let name = entry.path()?.into_owned();
+            let link_name = || -> Result<PathBuf> {
+                let target = entry.link_name()?;
+                target
+                    .map(|target| target.into_owned())
+                    .ok_or_else(|| anyhow!("no link target of `{}`", name.display()))
+            };
+
+            match entry.header().entry_type() {
+                EntryType::Directory => self.dir(&name)?,
+                EntryType::Regular | EntryType::Continuous => {
+                    let mode = entry.header().mode()?;
+                    self.file(&name, mode, &mut entry)?;
+                }
+                EntryType::Symlink => {
+                    let target = link_name()?;
+                    self.symlink(&name, &target)?;
+                }
+                EntryType::Link => {
+                    let target = link_name()?;
+                    self.hard_link(&name, &target)?;
+                }
+                // device files and the like are skipped
+                kind => trace!("skipping entry {}: {kind:?}", name.display()),
+            }
+        }
+        Ok(())
+    }
+
+    pub fn unpack_zip(&mut self, file: impl Read + Seek) -> Result<()> {
+        let mut archive = ZipArchive::new(file)?;
+        for i in 0..archive.len() {
+            let mut file = archive.by_index(i)?;
+            let name = PathBuf::from(file.name());
+
+            if file.is_dir() {
+                self.dir(&name)?;
+            } else if file.is_symlink() {
+                let target = read_link_target(&mut file)?;
+                self.symlink(&name, &target)?;
+            } else {
+                let mode = file.unix_mode().unwrap_or(0o644);
+                self.file(&name, mode, &mut file)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Unpack the file tree of a Debian package, which is an `ar` archive holding the `data.tar.*` member.
+    pub fn unpack_deb(&mut self, file: fs::File) -> Result<()> {
+        let mut archive = ar::Archive::new(file);
+        while let Some(entry) = archive.next_entry() {
+            let entry = entry?;
+            let name = String::from_utf8_lossy(entry.header().identifier())
+                .trim_end_matches('/')
+                .to_owned();
+            let Some(ext) = name.strip_prefix("data.tar") else {
+                continue;
+            };
+
+            trace!("unpacking debian data archive: {name}");
+            let compression = match ext.strip_prefix('.') {
+                Some(ext) =>
+                    Some(Compression::from_extension(ext).ok_or_else(|| anyhow!("unsupported data archive: {name}"))?),
+                None => None,
+            };
+            return self.unpack_tar(Compression::decoder(compression, entry)?);
+        }
+        bail!("no data archive found")
+    }
+
+    /// Unpack the file tree of an RPM package, which is a cpio archive following the lead and the headers.
+    pub fn unpack_rpm(&mut self, file: fs::File) -> Result<()> {
+        let mut reader = io::BufReader::new(file);
+
+        let mut lead = [0; 96];
+        reader.read_exact(&mut lead)?;
+        if lead[..4] != [0xed, 0xab, 0xee, 0xdb] {
+            bail!("invalid rpm lead");
+        }
+
+        // the signature header is padded to a multiple of 8 bytes, while the main header is not
+        for padded in [true, false] {
+            let mut header = [0; 16];
+            reader.read_exact(&mut header)?;
+            if header[..3] != [0x8e, 0xad, 0xe8] {
+                bail!("invalid rpm header");
+            }
+            let count = u32::from_be_bytes([header[8], header[9], header[10], header[11]]) as u64;
+            let size = u32::from_be_bytes([header[12], header[13], header[14], header[15]]) as u64;
+            let mut len = count * 16 + size;
+            if padded {
+                len = len.next_multiple_of(8);
+            }
+            io::copy(&mut reader.by_ref().take(len), &mut io::sink())?;
+        }
+
+        let compression = Compression::from_magic(reader.fill_buf()?);
+        trace!("unpacking rpm payload with compression: {compression:?}");
+        self.unpack_cpio(Compression::decoder(compression, reader)?)
+    }
+
+    /// Unpack a cpio archive in the `newc` format.
+    fn unpack_cpio(&mut self, mut reader: impl Read) -> Result<()> {
+        // the header and the file data are padded to multiples of 4 bytes
+        let skip_padding = |reader: &mut dyn Read, len: u64| -> io::Result<u64> {
+            io::copy(&mut reader.take(len.next_multiple_of(4) - len), &mut io::sink())
+        };
+
+        loop {
+            let mut header = [0; 110];
+            reader.read_exact(&mut header)?;
+            if !matches!(&header[..6], b"070701" | b"070702") {
+                bail!("unsupported cpio format");
+            }
+            let field = |i: usize| -> Result<u64> {
+                let field = std::str::from_utf8(&header[6 + i * 8..14 + i * 8])?;
+                Ok(u64::from_str_radix(field, 16)?)
+            };
+            let (mode, size, name_size) = (field(1)?, field(6)?, field(11)?);
+            // no sane filename is longer than `PATH_MAX`
+            if name_size > 4096 {
+                bail!("invalid filename size in cpio archive: {name_size}");
+            }
+
+            let mut name = Vec::new();
+            (&mut reader).take(name_size).read_to_end(&mut name)?;
+            skip_padding(&mut reader, 110 + name_size)?;
+            let name = String::from_utf8_lossy(&name).trim_end_matches('\0').to_owned();
+            if name == "TRAILER!!!" {
+                return Ok(());
+            }
+
+            let name = Path::new(&name);
+            let mut data = (&mut reader).take(size);
+            match mode & 0o170000 {
+                0o040000 => self.dir(name)?,
+                0o100000 => self.file(name, mode as u32, &mut data)?,
+                0o120000 => {
+                    let target = read_link_target(&mut data)?;
+                    self.symlink(name, &target)?;
+                }
+                // device files and the like are skipped
+                _ => trace!("skipping entry {}: {mode:o}", name.display()),
+            }
+            io::copy(&mut data, &mut io::sink())?;
+            skip_padding(&mut reader, size)?;
+        }
+    }
+
+    /// Check the symlinks extracted, which may escape through the symlinks extracted after them.
+    pub fn finish(self) -> Result<()> {
+        for entry in WalkDir::new(self.dir) {
+            let entry = entry?;
+            if entry.path_is_symlink() && !is_enclosed(self.dir, entry.path())? {
+                let link = entry.path().strip_prefix(self.dir)?;
+                bail!("symlink pointing outside of the archive: {}", link.display());
+            }
+        }
+        Ok(())
+    }
+
+    fn dir(&mut self, name: &Path) -> Result<()> {
+        let Some(path) = self.prepare(name)? else {
+            return Ok(());
+        };
+        if !path.is_dir() {
+            fs::create_dir(&path)?;
+        }
+        Ok(())
+    }
+
+    fn file(&mut self, name: &Path, mode: u32, reader: impl Read) -> Result<()> {
+        let Some(path) = self.prepare(name)? else {
+            bail!("invalid filename in archive: {}", name.display());
+        };
+
+        trace!("extracting file: {}", path.display());
+        // setuid, setgid and sticky bits are stripped
+        let mut output = fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .mode(mode & 0o777)
+            .open(&path)?;
+        // read one more byte than allowed to tell whether the limit is exceeded
+        let remaining = self.limits.size.saturating_sub(self.size);
+        self.size += io::copy(&mut reader.take(remaining.saturating_add(1)), &mut output)?;
+        if self.size > self.limits.size {
+            bail!(
+                "archive exceeds the size limit of {} bytes at `{}`",
+                self.limits.size,
+                name.display()
+            );
+        }
+        Ok(())
+    }
+
+    fn symlink(&mut self, name: &Path, target: &Path) -> Result<()> {
+        let Some(path) = self.prepare(name)? else {
+            bail!("invalid filename in archive: {}", name.display());
+        };
+
+        // a quick check on the target itself, the symlinks it goes through are checked after all extracted
+        let mut resolved = sanitize(name)?;
+        resolved.pop();
+        for component in target.components() {
+            match component {
+                Component::Normal(name) => resolved.push(name),
+                Component::CurDir => (),
+                Component::ParentDir if resolved.pop() => (),
+                _ => bail!(
+                    "symlink pointing outside of the archive: {} -> {}",
+                    name.display(),
+                    target.display()
+                ),
+            }
+        }
+
+        trace!("linking file: {} -> {}", path.display(), target.display());
+        std::os::unix::fs::symlink(target, &path)?;
+        Ok(())
+    }
+
+    fn hard_link(&mut self, name: &Path, target: &Path) -> Result<()> {
+        let Some(path) = self.prepare(name)? else {
+            bail!("invalid filename in archive: {}", name.display());
+        };
+        let target = sanitize(target)?;
+        self.check_parents(&target, false)?;
+
+        trace!("linking file: {} => {}", path.display(), target.display());
+        fs::hard_link(self.dir.join(target), &path)?;
+        Ok(())
+    }
+
+    /// Count the entry against the limits and prepare its path to extract to, which is `None` for the root.
+    fn prepare(&mut self, name: &Path) -> Result<Option<PathBuf>> {
+        self.files += 1;
+        if self.files > self.limits.files {
+            bail!(
+                "archive exceeds the limit of {} entries at `{}`",
+                self.limits.files,
+                name.display()
+            );
+        }
+
+        let entry = sanitize(name)?;
+        if entry.as_os_str().is_empty() {
+            return Ok(None);
+        }
+        self.check_parents(&entry, true)?;
+
+        // replace the existing entry instead of writing through it
+        let path = self.dir.join(&entry);
+        if let Ok(metadata) = fs::symlink_metadata(&path) {
+            if !metadata.is_dir() {
+                fs::remove_file(&path)?;
+            }
+        }
+        Ok(Some(path))
+    }
+
+    /// Check the parent directories of the entry are not symlinks, creating the missing ones if required.
+    fn check_parents(&self, entry: &Path, create: bool) -> Result<()> {
+        let mut path = self.dir.to_path_buf();
+        for component in entry.parent().into_iter().flat_map(Path::components) {
+            path.push(component);
+            match fs::symlink_metadata(&path) {
+                Ok(metadata) if metadata.is_symlink() => {
+                    bail!("entry extracted through a symlink: {}", entry.display())
+                }
+                Ok(_) => (),
+                Err(e) if e.kind() == io::ErrorKind::NotFound && create => fs::create_dir(&path)?,
+                Err(e) => return Err(e.into()),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Get the relative path of an archive entry, refusing absolute paths and `..` components.
+fn sanitize(name: &Path) -> Result<PathBuf> {
+    let mut path = PathBuf::new();
+    for component in name.components() {
+        match component {
+            Component::Normal(name) => path.push(name),
+            Component::CurDir => (),
+            Component::RootDir | Component::Prefix(_) => bail!("absolute path in archive: {}", name.display()),
+            Component::ParentDir => bail!("path traversal in archive: {}", name.display()),
+        }
+    }
+    Ok(path)
+}
+
+fn read_link_target(reader: impl Read) -> Result<PathBuf> {
+    let mut target = String::new();
+    // no sane link target is longer than `PATH_MAX`
+    reader.take(4096).read_to_string(&mut target)?;
+    Ok(target.into())
+}
+
+/// Check whether the symlink resolves to a path under the directory, following the symlinks in its target.
+///
+/// The parent directories of the symlink are expected to be real directories under the directory.
+fn is_enclosed(dir: &Path, link: &Path) -> Result<bool> {
+    let mut resolved = link.strip_prefix(dir)?.to_path_buf();
+    let mut pending = VecDeque::new();
+    let mut hops = 0;
+    loop {
+        if fs::symlink_metadata(dir.join(&resolved)).is_ok_and(|metadata| metadata.is_symlink()) {
+            hops += 1;
+            if hops > MAX_SYMLINK_HOPS {
+                return Ok(false);
+            }
+            let target = fs::read_link(dir.join(&resolved))?;
+            resolved.pop();
+            for component in target.components().rev() {
+                pending.push_front(component.as_os_str().to_owned());
+            }
+        }
+
+        let Some(component) = pending.pop_front() else {
+            return Ok(true);
+        };
+        match Path::new(&component).components().next() {
+            Some(Component::Normal(name)) => resolved.push(name),
+            Some(Component::CurDir) => (),
+            Some(Component::ParentDir) if resolved.pop() => (),
+            _ => return Ok(false),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io::Cursor, os::unix::fs::PermissionsExt as _};
+
+    use tar::{Builder, Header};
+    use zip::{write::SimpleFileOptions, ZipWriter};
+
+    use super::*;
+    use crate::util::temp::scratch;
+
+    enum Entry<'a> {
+        Dir(&'a str),
+        File(&'a str, u32, &'a [u8]),
+        Symlink(&'a str, &'a str),
+        HardLink(&'a str, &'a str),
+    }
+    use Entry::*;
+
+    /// Build a tar archive, writing the names as they are to allow the malicious ones.
+    fn tar(entries: &[Entry]) -> Vec<u8> {
+        let mut builder = Builder::new(Vec::new());
+        for entry in entries {
+            let mut header = Header::new_gnu();
+            let (name, data): (_, &[u8]) = match *entry {
+                Dir(name) => {
+                    header.set_entry_type(EntryType::Directory);
+                    header.set_mode(0o755);
+                    (name, &[])
+                }
+                File(name, mode, data) => {
+                    header.set_mode(mode);
+                    (name, data)
+                }
+                Symlink(name, target) | HardLink(name, target) => {
+                    let kind = if matches!(entry, Symlink(..)) {
+                        EntryType::Symlink
+                    } else {
+                        EntryType::Link
+                    };
+                    header.set_entry_type(kind);
+                    header.set_link_name(target).unwrap();
+                    (name, &[])
+                }
+            };
+            header.as_old_mut().name[..name.len()].copy_from_slice(name.as_bytes());
+            header.set_size(data.len() as u64);
+            header.set_cksum();
+            builder.append(&header, data).unwrap();
+        }
+        builder.into_inner().unwrap()
+    }
+
+    fn zip(entries: &[Entry]) -> Vec<u8> {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        for entry in entries {
+            let options = SimpleFileOptions::default();
+            match *entry {
+                Dir(name) => writer.add_directory(name, options).unwrap(),
+                File(name, mode, data) => {
+                    writer.start_file(name, options.unix_permissions(mode)).unwrap();
+                    io::Write::write_all(&mut writer, data).unwrap();
+                }
+                Symlink(name, target) => writer.add_symlink(name, target, options).unwrap(),
+                HardLink(..) => panic!("zip archives have no hard links"),
+            }
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    /// Build a cpio archive in the `newc` format.
+    fn cpio(entries: &[Entry]) -> Vec<u8> {
+        let mut archive = Vec::new();
+        let mut append = |name: &str, mode: u32, data: &[u8]| {
+            let name_size = name.len() + 1;
+            let fields = [0, mode, 0, 0, 1, 0, data.len() as u32, 0, 0, 0, 0, name_size as u32, 0];
+            archive.extend(b"070701");
+            for field in fields {
+                archive.extend(format!("{field:08x}").as_bytes());
+            }
+            archive.extend(name.as_bytes());
+            archive.push(0);
+            // each entry starts aligned, so the header and the data are padded by aligning the archive
+            archive.resize(archive.len().next_multiple_of(4), 0);
+            archive.extend(data);
+            archive.resize(archive.len().next_multiple_of(4), 0);
+        };
+        for entry in entries {
+            match *entry {
+                Dir(name) => append(name, 0o040755, &[]),
+                File(name, mode, data) => append(name, 0o100000 | mode, data),
+                Symlink(name, target) => append(name, 0o120777, target.as_bytes()),
+                HardLink(..) => panic!("hard links are not supported in cpio archives"),
+            }
+        }
+        append("TRAILER!!!", 0, &[]);
+        archive
+    }
+
+    fn unpack_tar(dir: &Path, limits: Limits, entries: &[Entry]) -> Result<()> {
+        let mut extractor = Extractor::new(dir, limits);
+        extractor.unpack_tar(&tar(entries)[..])?;
+        extractor.finish()
+    }
+
+    fn unpack_zip(dir: &Path, entries: &[Entry]) -> Result<()> {
+        let mut extractor = Extractor::new(dir, Limits::default());
+        extractor.unpack_zip(Cursor::new(zip(entries)))?;
+        extractor.finish()
+    }
+
+    fn unpack_cpio(dir: &Path, entries: &[Entry]) -> Result<()> {
+        let mut extractor = Extractor::new(dir, Limits::default());
+        extractor.unpack_cpio(&cpio(entries)[..])?;
+        extractor.finish()
+    }
+
+    fn mode(path: &Path) -> u32 {
+        fs::symlink_metadata(path).unwrap().permissions().mode() & 0o7777
+    }
+
+    #[test]
+    fn tar_extracts_entries() {
+        let tmp = scratch("tar");
+        let dir = tmp.path();
+        let entries = [
+            Dir("./tool/"),
+            File("./tool/bin", 0o755, b"binary"),
+            Symlink("tool/link", "bin"),
+            HardLink("tool/hard", "tool/bin"),
+            Symlink("a", "tool"),
+        ];
+        unpack_tar(dir, Limits::default(), &entries).unwrap();
+
+        assert_eq!(fs::read(dir.join("tool/bin")).unwrap(), b"binary");
+        assert_eq!(mode(&dir.join("tool/bin")), 0o755);
+        assert_eq!(fs::read_link(dir.join("tool/link")).unwrap(), Path::new("bin"));
+        assert_eq!(fs::read(dir.join("tool/hard")).unwrap(), b"binary");
+        assert_eq!(fs::read(dir.join("a/bin")).unwrap(), b"binary");
+    }
+
+    #[test]
+    fn tar_refuses_path_traversal() {
+        let tmp = scratch("tar-traversal");
+        let err = unpack_tar(tmp.path(), Limits::default(), &[File("../evil", 0o644, b"")]).unwrap_err();
+        assert_eq!(err.to_string(), "path traversal in archive: ../evil");
+
+        let err = unpack_tar(tmp.path(), Limits::default(), &[File("a/../../evil", 0o644, b"")]).unwrap_err();
+        assert_eq!(err.to_string(), "path traversal in archive: a/../../evil");
+    }
+
+    #[test]
+    fn tar_refuses_absolute_paths() {
+        let tmp = scratch("tar-absolute");
+        let err = unpack_tar(tmp.path(), Limits::default(), &[File("/tmp/evil", 0o644, b"")]).unwrap_err();
+        assert_eq!(err.to_string(), "absolute path in archive: /tmp/evil");
+
+        let err = unpack_tar(tmp.path(), Limits::default(), &[HardLink("passwd", "/etc/passwd")]).unwrap_err();
+        assert_eq!(err.to_string(), "absolute path in archive: /etc/passwd");
+    }
+
+    #[test]
+    fn tar_refuses_escaping_symlinks() {
+        let tmp = scratch("tar-symlink");
+        let err = unpack_tar(tmp.path(), Limits::default(), &[Dir("sub"), Symlink("sub/up", "../..")]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "symlink pointing outside of the archive: sub/up -> ../.."
+        );
+
+        let tmp = scratch("tar-symlink-parent");
+        let err = unpack_tar(tmp.path(), Limits::default(), &[Symlink("sub", "..")]).unwrap_err();
+        assert_eq!(err.to_string(), "symlink pointing outside of the archive: sub -> ..");
+
+        let tmp = scratch("tar-symlink-nested");
+        let entries = [Dir("a"), Symlink("x", "a/../..")];
+        let err = unpack_tar(tmp.path(), Limits::default(), &entries).unwrap_err();
+        assert_eq!(err.to_string(), "symlink pointing outside of the archive: x -> a/../..");
+
+        let tmp = scratch("tar-symlink-absolute");
+        let err = unpack_tar(tmp.path(), Limits::default(), &[Symlink("etc", "/etc")]).unwrap_err();
+        assert_eq!(err.to_string(), "symlink pointing outside of the archive: etc -> /etc");
+    }
+
+    #[test]
+    fn tar_refuses_symlinks_escaping_through_others() {
+        // `a` looks enclosed by itself, but `d` resolves to the root, so `a` points to its parent
+        let tmp = scratch("tar-symlink-chain");
+        let entries = [Symlink("a", "d/.."), Symlink("d", ".")];
+        let err = unpack_tar(tmp.path(), Limits::default(), &entries).unwrap_err();
+        assert_eq!(err.to_string(), "symlink pointing outside of the archive: a");
+
+        let tmp = scratch("tar-symlink-loop");
+        let entries = [Symlink("a", "b"), Symlink("b", "a")];
+        let err = unpack_tar(tmp.path(), Limits::default(), &entries).unwrap_err();
+        assert!(err.to_string().starts_with("symlink pointing outside of the archive: "));
+    }
+
+    #[test]
+    fn tar_refuses_writing_through_symlinks() {
+        let tmp = scratch("tar-through");
+        let entries = [Dir("sub"), Symlink("a", "sub"), File("a/file", 0o644, b"")];
+        let err = unpack_tar(tmp.path(), Limits::default(), &entries).unwrap_err();
+        assert_eq!(err.to_string(), "entry extracted through a symlink: a/file");
+        assert!(!tmp.path().join("sub/file").exists());
+
+        let tmp = scratch("tar-through-link");
+        let entries = [
+            Dir("sub"),
+            File("sub/file", 0o644, b""),
+            Symlink("a", "sub"),
+            HardLink("hard", "a/file"),
+        ];
+        let err = unpack_tar(tmp.path(), Limits::default(), &entries).unwrap_err();
+        assert_eq!(err.to_string(), "entry extracted through a symlink: a/file");
+    }
+
+    #[test]
+    fn tar_replaces_symlinks_instead_of_writing_through_them() {
+        let tmp = scratch("tar-replace");
+        let dir = tmp.path();
+        let entries = [
+            File("file", 0o644, b"old"),
+            Symlink("link", "file"),
+            File("link", 0o644, b"new"),
+        ];
+        unpack_tar(dir, Limits::default(), &entries).unwrap();
+
+        assert_eq!(fs::read(dir.join("file")).unwrap(), b"old");
+        assert_eq!(fs::read(dir.join("link")).unwrap(), b"new");
+        assert!(!dir.join("link").is_symlink());
+    }
+
+    #[test]
+    fn tar_strips_setuid_bits() {
+        let tmp = scratch("tar-setuid");
+        let dir = tmp.path();
+        let entries = [
+            File("setuid", 0o4755, b""),
+            File("setgid", 0o2755, b""),
+            File("sticky", 0o1644, b""),
+        ];
+        unpack_tar(dir, Limits::default(), &entries).unwrap();
+
+        assert_eq!(mode(&dir.join("setuid")), 0o755);
+        assert_eq!(mode(&dir.join("setgid")), 0o755);
+        assert_eq!(mode(&dir.join("sticky")), 0o644);
+    }
+
+    #[test]
+    fn tar_enforces_size_limit() {
+        let limits = Limits { size: 8, files: 10 };
+        let tmp = scratch("tar-size");
+        unpack_tar(tmp.path(), limits, &[
+            File("a", 0o644, b"1234"),
+            File("b", 0o644, b"5678"),
+        ])
+        .unwrap();
+
+        let tmp = scratch("tar-size-exceeded");
+        let entries = [File("a", 0o644, b"1234"), File("b", 0o644, b"56789")];
+        let err = unpack_tar(tmp.path(), limits, &entries).unwrap_err();
+        assert_eq!(err.to_string(), "archive exceeds the size limit of 8 bytes at `b`");
+    }
+
+    #[test]
+    fn tar_enforces_files_limit() {
+        let limits = Limits { size: 8, files: 2 };
+        let tmp = scratch("tar-files");
+        unpack_tar(tmp.path(), limits, &[Dir("a"), File("a/b", 0o644, b"")]).unwrap();
+
+        let tmp = scratch("tar-files-exceeded");
+        let entries = [Dir("a"), File("a/b", 0o644, b""), Symlink("c", "a")];
+        let err = unpack_tar(tmp.path(), limits, &entries).unwrap_err();
+        assert_eq!(err.to_string(), "archive exceeds the limit of 2 entries at `c`");
+    }
+
+    #[test]
+    fn zip_extracts_entries() {
+        let tmp = scratch("zip");
+        let dir = tmp.path();
+        let entries = [
+            Dir("tool/"),
+            File("tool/bin", 0o4755, b"binary"),
+            Symlink("tool/link", "bin"),
+        ];
+        unpack_zip(dir, &entries).unwrap();
+
+        assert_eq!(fs::read(dir.join("tool/bin")).unwrap(), b"binary");
+        assert_eq!(mode(&dir.join("tool/bin")), 0o755);
+        assert_eq!(fs::read_link(dir.join("tool/link")).unwrap(), Path::new("bin"));
+    }
+
+    #[test]
+    fn zip_refuses_unsafe_entries() {
+        let tmp = scratch("zip-traversal");
+        let err = unpack_zip(tmp.path(), &[File("../evil", 0o644, b"")]).unwrap_err();
+        assert_eq!(err.to_string(), "path traversal in archive: ../evil");
+
+        let tmp = scratch("zip-symlink");
+        let err = unpack_zip(tmp.path(), &[Symlink("sub", "..")]).unwrap_err();
+        assert_eq!(err.to_string(), "symlink pointing outside of the archive: sub -> ..");
+
+        let tmp = scratch("zip-through");
+        let err = unpack_zip(tmp.path(), &[
+            Dir("sub/"),
+            Symlink("a", "sub"),
+            File("a/file", 0o644, b""),
+        ])
+        .unwrap_err();
+        assert_eq!(err.to_string(), "entry extracted through a symlink: a/file");
+    }
+
+    #[test]
+    fn cpio_extracts_entries() {
+        let tmp = scratch("cpio");
+        let dir = tmp.path();
+        let entries = [
+            Dir("./usr"),
+            File("./usr/bin", 0o6755, b"binary"),
+            Symlink("./usr/link", "bin"),
+        ];
+        unpack_cpio(dir, &entries).unwrap();
+
+        assert_eq!(fs::read(dir.join("usr/bin")).unwrap(), b"binary");
+        assert_eq!(mode(&dir.join("usr/bin")), 0o755);
+        assert_eq!(fs::read_link(dir.join("usr/link")).unwrap(), Path::new("bin"));
+    }
+
+    #[test]
+    fn cpio_refuses_unsafe_entries() {
+        let tmp = scratch("cpio-traversal");
+        let err = unpack_cpio(tmp.path(), &[File("../evil", 0o644, b"")]).unwrap_err();
+        assert_eq!(err.to_string(), "path traversal in archive: ../evil");
+
+        let tmp = scratch("cpio-absolute");
+        let err = unpack_cpio(tmp.path(), &[File("/evil", 0o644, b"")]).unwrap_err();
+        assert_eq!(err.to_string(), "absolute path in archive: /evil");
+
+        let tmp = scratch("cpio-symlink");
+        let err = unpack_cpio(tmp.path(), &[Symlink("x", "a/../..")]).unwrap_err();
+        assert_eq!(err.to_string(), "symlink pointing outside of the archive: x -> a/../..");
+    }
+
+    #[test]
+    fn cpio_refuses_oversized_names() {
+        let tmp = scratch("cpio-name-size");
+        let mut archive = cpio(&[File("evil", 0o644, b"")]);
+        // the name size is the twelfth field after the magic
+        archive[94..102].copy_from_slice(b"ffffffff");
+        let mut extractor = Extractor::new(tmp.path(), Limits::default());
+        let err = extractor.unpack_cpio(&archive[..]).unwrap_err();
+        assert_eq!(err.to_string(), "invalid filename size in cpio archive: 4294967295");
+    }
+
+    #[test]
+    fn sanitize_paths() {
+        assert_eq!(sanitize(Path::new("./a/./b")).unwrap(), Path::new("a/b"));
+        assert_eq!(sanitize(Path::new("./")).unwrap(), Path::new(""));
+        assert!(sanitize(Path::new("a/../b")).is_err());
+        assert!(sanitize(Path::new("/a")).is_err());
+    }
+}
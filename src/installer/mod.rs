@@ -0,0 +1,607 @@
+mod extract;
+
+use std::{
+    self,
+    collections::BTreeMap,
+    error,
+    ffi::OsStr,
+    fmt,
+    fs::{self},
+    os::unix::fs::PermissionsExt,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    time::SystemTime,
+};
+
+use anyhow::{anyhow, bail, Context as _};
+use itertools::Itertools;
+use tracing::{debug, trace};
+use walkdir::WalkDir;
+
+use crate::{
+    config::{Bins, LockedPackage, Package},
+    context::Context,
+    util::{elf::Elf, load_toml, mkdir_p, remove_file_if_exists, rm_rf, symlink_temp, temp::TempDir, Shorten},
+};
+
+use extract::Extractor;
+
+/// The file recording the installed package in its install directory.
+const RECEIPT: &str = ".rpk-package.toml";
+
+pub use extract::Compression;
+
+/// The error of a linked binary which can't run on the target platform.
+#[derive(Debug)]
+pub struct IncompatibleBinary(String);
+
+impl fmt::Display for IncompatibleBinary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl error::Error for IncompatibleBinary {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveKind {
+    Plain(Option<Compression>),
+    Tar(Option<Compression>),
+    Zip,
+    /// A Debian package, whose file tree is in the `data.tar.*` member.
+    Deb,
+    /// An RPM package, whose file tree is in the cpio payload.
+    Rpm,
+    /// A self-contained executable, which can also extract its squashfs image itself.
+    AppImage,
+}
+
+pub fn detect_archive(path: impl AsRef<Path>) -> anyhow::Result<ArchiveKind> {
+    let path = path.as_ref();
+    let ext = path.extension().map(|ext| ext.try_into()).transpose()?;
+
+    trace!("detecting archive type using extension: {:?}", ext);
+
+    let kind = match ext {
+        Some("zip") => ArchiveKind::Zip,
+        Some("deb") => ArchiveKind::Deb,
+        Some("rpm") => ArchiveKind::Rpm,
+        Some("tar") => ArchiveKind::Tar(None),
+        Some("tgz") => ArchiveKind::Tar(Some(Compression::Gzip)),
+        Some("txz") => ArchiveKind::Tar(Some(Compression::Xz)),
+        Some("tbz" | "tbz2") => ArchiveKind::Tar(Some(Compression::Bzip2)),
+        Some("tzst") => ArchiveKind::Tar(Some(Compression::Zstd)),
+        Some(ext @ ("gz" | "xz" | "bz2" | "zst")) => {
+            let compression = Compression::from_extension(ext);
+            match path
+                .file_stem()
+                .map(Path::new)
+                .and_then(|f| f.extension())
+                .map(|ext| ext.try_into())
+                .transpose()?
+            {
+                Some("tar") => ArchiveKind::Tar(compression),
+                _ => ArchiveKind::Plain(compression),
+            }
+        }
+        Some(ext) if ext.eq_ignore_ascii_case("appimage") => ArchiveKind::AppImage,
+        _ => ArchiveKind::Plain(None),
+    };
+
+    debug!("detected archive type: {:?}", kind);
+
+    Ok(kind)
+}
+
+/// Install the package from the cached asset and link its files as declared by the package, or as recorded in the
+/// locked package if not given. Returns the locked package with the linked files recorded.
+pub fn install_package(ctx: &Context, lpkg: &LockedPackage, pkg: Option<&Package>) -> anyhow::Result<LockedPackage> {
+    // the paths locked per platform may be recorded on another one whose asset is laid out differently, so its
+    // binaries are looked up by name and its other files are detected again, unless skipped
+    let per_platform = !lpkg.targets.is_empty();
+    let locked_bins = (!lpkg.bins.is_empty()).then(|| match per_platform {
+        true => Bins::Map(
+            lpkg.bins
+                .iter()
+                .map(|(name, path)| {
+                    let file_name = Path::new(path).file_name().map(|s| s.to_string_lossy().into_owned());
+                    (name.clone(), file_name.unwrap_or_else(|| name.clone()))
+                })
+                .collect(),
+        ),
+        false => Bins::Map(lpkg.bins.clone()),
+    });
+    let (bins, completions, manpages, extract_appimage) = match pkg {
+        Some(pkg) => (
+            pkg.bins.as_ref(),
+            pkg.completions.as_deref(),
+            pkg.manpages.as_deref(),
+            pkg.extract_appimage,
+        ),
+        // files are detected again for lock files without them
+        None => (
+            locked_bins.as_ref(),
+            lpkg.completions
+                .as_deref()
+                .filter(|files| !per_platform || files.is_empty()),
+            lpkg.manpages
+                .as_deref()
+                .filter(|files| !per_platform || files.is_empty()),
+            lpkg.extract_appimage,
+        ),
+    };
+
+    let file = &ctx.cache_dir.join(&lpkg.filename);
+    let install_dir = ctx.data_dir.join(&lpkg.name).join(&lpkg.version);
+
+    let archive = detect_archive(file)?;
+    let file = std::fs::File::open(file)?;
+
+    // extract into a staging directory, so that the installed files are untouched until everything succeeds
+    let staging = TempDir::new_force(&install_dir)?;
+    let staging_dir = staging.path().to_owned();
+
+    // the default binary of the archive, if not found by the package name
+    let mut default_bin = None;
+    let mut extractor = Extractor::new(&staging_dir, ctx.limits);
+    match archive {
+        ArchiveKind::Plain(compression) => extractor.unpack_file(&lpkg.name, Compression::decoder(compression, file)?),
+        ArchiveKind::Zip => extractor.unpack_zip(file),
+        ArchiveKind::Tar(compression) => extractor.unpack_tar(Compression::decoder(compression, file)?),
+        // maintainer scripts are never run, only the file tree is extracted
+        ArchiveKind::Deb => extractor.unpack_deb(file),
+        ArchiveKind::Rpm => extractor.unpack_rpm(file),
+        ArchiveKind::AppImage => extractor.unpack_file(&lpkg.name, file).and_then(|()| {
+            if extract_appimage {
+                extract_appimage_into(&staging_dir.join(&lpkg.name), &staging_dir)?;
+                // the extracted `squashfs-root` is trimmed below as the single directory
+                default_bin = Some(PathBuf::from("AppRun"));
+            }
+            Ok(())
+        }),
+    }
+    .context(format!("failed to unpack {}", lpkg.filename))?;
+
+    // Some archives contain only a single directory, move its contents to the install directory
+    let files: Vec<_> = fs::read_dir(&staging_dir)?.try_collect()?;
+
+    match &files[..] {
+        [] => bail!("no files found in archive {}", lpkg.filename),
+        [entry] if entry.file_type()?.is_file() => default_bin = Some(PathBuf::from(entry.file_name())),
+        [entry] if entry.file_type()?.is_dir() => {
+            // move the directory aside first in case it contains an entry of the same name
+            let prefix_dir = staging_dir.join(".rpk-prefix");
+            fs::rename(entry.path(), &prefix_dir)?;
+            for entry in fs::read_dir(&prefix_dir)? {
+                let path = entry?.path();
+                let name = path
+                    .file_name()
+                    .ok_or_else(|| anyhow!("invalid filename: {:?}", path.file_name()))?;
+                let install_path = staging_dir.join(name);
+
+                trace!("trim extra prefix: {:?} -> {:?}", path, install_path);
+                fs::rename(&path, &install_path)?;
+            }
+            fs::remove_dir(&prefix_dir)?;
+        }
+        _ => (),
+    }
+    // symlinks are checked after the trimming, which moves them
+    extractor
+        .finish()
+        .context(format!("failed to unpack {}", lpkg.filename))?;
+
+    let mut bin_paths = BTreeMap::new();
+    match bins {
+        Some(bins) =>
+            for (name, bin) in bins.entries() {
+                if name.is_empty() || name.contains('/') {
+                    bail!("invalid binary name: '{name}'");
+                }
+                let path = find_binary(ctx, &staging_dir, bin)?
+                    .ok_or_else(|| anyhow!("binary '{bin}' not found in archive {}", lpkg.filename))?;
+                bin_paths.insert(name.to_owned(), path);
+            },
+        None => {
+            // a single file archive contains the binary itself, whatever its name is
+            let path = match default_bin {
+                Some(path) => Some(path),
+                None => find_binary(ctx, &staging_dir, &lpkg.name)?,
+            };
+            let path = path.ok_or_else(|| anyhow!("no binary found in archive"))?;
+            bin_paths.insert(lpkg.name.clone(), path);
+        }
+    }
+
+    let mut installed = lpkg.clone();
+    installed.bins = BTreeMap::new();
+    let mut links = Vec::new();
+    for (name, path) in bin_paths {
+        let bin_path = staging_dir.join(&path);
+        check_binary(ctx, &name, &bin_path)?;
+
+        let mut perms = fs::metadata(&bin_path)?.permissions();
+        perms.set_mode(perms.mode() | 0o111);
+        fs::set_permissions(&bin_path, perms)?;
+
+        links.push((install_dir.join(&path), ctx.bin_dir.join(&name)));
+        installed.bins.insert(name, path_to_string(&path)?);
+    }
+
+    let shared_completions = find_shared_files(&staging_dir, completions, "completion", completion_link)?;
+    let shared_manpages = find_shared_files(&staging_dir, manpages, "man page", manpage_link)?;
+    for (path, dest) in shared_completions.iter().chain(&shared_manpages) {
+        links.push((install_dir.join(path), ctx.share_dir.join(dest)));
+    }
+
+    let paths = |files: Vec<(PathBuf, PathBuf)>| -> anyhow::Result<Vec<_>> {
+        files.iter().map(|(path, _)| path_to_string(path)).try_collect()
+    };
+    let (shared_completions, shared_manpages) = (paths(shared_completions)?, paths(shared_manpages)?);
+    // nothing is recorded if nothing is declared or detected
+    installed.completions = Some(shared_completions).filter(|files| completions.is_some() || !files.is_empty());
+    installed.manpages = Some(shared_manpages).filter(|files| manpages.is_some() || !files.is_empty());
+    installed.extract_appimage = extract_appimage;
+
+    // the installed package is recorded along with its files, so that it can be switched back to offline
+    let receipt = toml::to_string_pretty(&installed).context("failed to serialize the installed package")?;
+    fs::write(staging_dir.join(RECEIPT), receipt)?;
+
+    // the staging directory is swapped in before the links are renamed into place, so that a failure leaves the
+    // previously installed version as it was
+    link_files(&links, || staging.persist())?;
+
+    Ok(installed)
+}
+
+/// Switch the links of the package to another installed version, returns the package recorded when it was installed.
+///
+/// The links to the files of other versions which are not replaced are removed.
+pub fn use_package(ctx: &Context, name: &str, version: &str) -> anyhow::Result<LockedPackage> {
+    let pkg_dir = ctx.data_dir.join(name);
+    let install_dir = pkg_dir.join(version);
+    let receipt = install_dir.join(RECEIPT);
+    if !install_dir.is_dir() {
+        let versions = installed_versions(ctx, name)?
+            .into_iter()
+            .map(|(version, _)| version)
+            .join(", ");
+        match versions.is_empty() {
+            true => bail!("no version of {name} is installed"),
+            false => bail!("{name}@{version} is not installed, the installed versions are: {versions}"),
+        }
+    }
+    if !receipt.is_file() {
+        bail!("{name}@{version} was installed by an older version of rpk, install it again to switch to it");
+    }
+    let mut lpkg: LockedPackage =
+        load_toml(&receipt).with_context(|| format!("failed to load {}", receipt.display()))?;
+    lpkg.name = name.to_owned();
+
+    let mut links = Vec::new();
+    for (bin, path) in &lpkg.bins {
+        links.push((install_dir.join(path), ctx.bin_dir.join(bin)));
+    }
+    let shared = [
+        (&lpkg.completions, completion_link as fn(&Path) -> Option<PathBuf>),
+        (&lpkg.manpages, manpage_link),
+    ];
+    for (files, link) in shared {
+        for file in files.iter().flatten() {
+            let dest = link(Path::new(file)).ok_or_else(|| anyhow!("cannot determine where to link '{file}'"))?;
+            links.push((install_dir.join(file), ctx.share_dir.join(dest)));
+        }
+    }
+
+    let stale = find_links(ctx)?
+        .into_iter()
+        .filter(|(path, target)| target.starts_with(&pkg_dir) && links.iter().all(|(_, dst)| dst != path))
+        .collect_vec();
+    link_files(&links, || Ok(()))?;
+    for (path, target) in stale {
+        fs::remove_file(&path)?;
+        debug!("link removed: '{}' -> '{}'", path.display(), target.display());
+    }
+
+    Ok(lpkg)
+}
+
+/// List the installed versions of the package, along with their install time, the most recent first.
+pub fn installed_versions(ctx: &Context, name: &str) -> anyhow::Result<Vec<(String, SystemTime)>> {
+    let pkg_dir = ctx.data_dir.join(name);
+    if !pkg_dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut versions = Vec::new();
+    for entry in fs::read_dir(&pkg_dir)? {
+        let entry = entry?;
+        // the staging directories of unfinished installations are not versions
+        let Some(version) = entry
+            .file_name()
+            .to_str()
+            .filter(|name| !name.starts_with('~'))
+            .map(ToOwned::to_owned)
+        else {
+            continue;
+        };
+        let metadata = entry.metadata()?;
+        if metadata.is_dir() {
+            versions.push((version, metadata.modified()?));
+        }
+    }
+    versions.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    Ok(versions)
+}
+
+/// Link the files, which are prepared under temporary names and renamed into place only after `commit` succeeds, so
+/// that a failure leaves the existing links as they were.
+fn link_files(links: &[(PathBuf, PathBuf)], commit: impl FnOnce() -> anyhow::Result<()>) -> anyhow::Result<()> {
+    let mut pending = Vec::new();
+    let prepared = links
+        .iter()
+        .try_for_each(|(src, dst)| -> anyhow::Result<()> {
+            if let Some(parent) = dst.parent() {
+                mkdir_p(parent)?;
+            }
+            pending.push((symlink_temp(src, dst)?, dst));
+            Ok(())
+        })
+        .and_then(|()| commit());
+    if let Err(e) = prepared {
+        for (temp, _) in &pending {
+            remove_file_if_exists(temp)?;
+        }
+        return Err(e);
+    }
+    for (temp, dst) in pending {
+        fs::rename(&temp, dst)?;
+        debug!("link built: '{}' -> '{}'", fs::read_link(dst)?.display(), dst.display());
+    }
+    Ok(())
+}
+
+fn path_to_string(path: &Path) -> anyhow::Result<String> {
+    path.to_str()
+        .map(ToOwned::to_owned)
+        .ok_or_else(|| anyhow!("invalid path: {}", path.display()))
+}
+
+/// Find the man pages or shell completions to link into the share directory, either the declared ones or those detected
+/// in the install directory. Returns the files relative to the install directory, paired with their destinations.
+///
+/// The destination of a file relative to the share directory is given by `dest`, which returns `None` if the file is
+/// not of the kind.
+fn find_shared_files(
+    install_dir: &Path,
+    declared: Option<&[String]>,
+    kind: &str,
+    dest: impl Fn(&Path) -> Option<PathBuf>,
+) -> anyhow::Result<Vec<(PathBuf, PathBuf)>> {
+    match declared {
+        Some(files) => files
+            .iter()
+            .map(|file| {
+                let path = PathBuf::from(file);
+                if !install_dir.join(&path).is_file() {
+                    bail!("{kind} '{file}' not found in archive");
+                }
+                let dest = dest(&path).ok_or_else(|| anyhow!("cannot determine the type of {kind} '{file}'"))?;
+                Ok((path, dest))
+            })
+            .try_collect(),
+        None => WalkDir::new(install_dir)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_ok(|entry| entry.file_type().is_file())
+            .map_ok(|entry| entry.into_path())
+            .map(|path| -> anyhow::Result<_> {
+                let path = path?.strip_prefix(install_dir)?.to_owned();
+                Ok(dest(&path).map(|dest| (path, dest)))
+            })
+            .flatten_ok()
+            .try_collect(),
+    }
+}
+
+/// The path to link the completion file to, relative to the share directory, `None` if it's not a completion file.
+fn completion_link(path: &Path) -> Option<PathBuf> {
+    completion_shell(path).map(|shell| shell.link_path(path))
+}
+
+/// The path to link the man page to, relative to the share directory, `None` if it's not a man page.
+fn manpage_link(path: &Path) -> Option<PathBuf> {
+    let name = path.file_name()?.to_str()?;
+    man_section(name).map(|section| PathBuf::from(format!("man/man{section}")).join(name))
+}
+
+/// Get the section of a man page from its extension, like `fd.1` or `fd.1.gz`.
+fn man_section(name: &str) -> Option<char> {
+    let name = name.strip_suffix(".gz").unwrap_or(name);
+    // not to be confused with versioned shared libraries like `libfoo.so.1`
+    if name.contains(".so.") {
+        return None;
+    }
+    let (_, ext) = name.rsplit_once('.')?;
+    match ext.as_bytes() {
+        [c @ b'1'..=b'9'] => Some(*c as char),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CompletionShell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+/// Detect the shell of a completion file, which is expected to live in a directory for completions like `complete`,
+/// `autocomplete` or `completions/zsh`.
+fn completion_shell(path: &Path) -> Option<CompletionShell> {
+    let name = path.file_name()?.to_str()?;
+    let dirs: Vec<_> = path
+        .parent()?
+        .iter()
+        .filter_map(|dir| dir.to_str())
+        .map(str::to_lowercase)
+        .collect();
+    if !dirs
+        .iter()
+        .any(|dir| dir.contains("complet") || ["bash", "zsh", "fish"].contains(&dir.as_str()))
+    {
+        return None;
+    }
+
+    match name.rsplit_once('.').map(|(_, ext)| ext) {
+        Some("bash" | "bash-completion") => Some(CompletionShell::Bash),
+        Some("zsh") => Some(CompletionShell::Zsh),
+        Some("fish") => Some(CompletionShell::Fish),
+        _ if name.starts_with('_') && !name.contains('.') => Some(CompletionShell::Zsh),
+        Some(_) => None,
+        // the closest directory naming the shell, like `completions/bash` or `bash-completion/completions`
+        None => dirs.iter().rev().find_map(|dir| match dir.as_str() {
+            "bash" | "bash-completion" => Some(CompletionShell::Bash),
+            "zsh" => Some(CompletionShell::Zsh),
+            "fish" => Some(CompletionShell::Fish),
+            _ => None,
+        }),
+    }
+}
+
+impl CompletionShell {
+    /// The path to link the completion file to, relative to the share directory.
+    fn link_path(self, path: &Path) -> PathBuf {
+        let name = path.file_name().and_then(|name| name.to_str()).unwrap_or_default();
+        let stem = name.rsplit_once('.').map_or(name, |(stem, _)| stem);
+        match self {
+            CompletionShell::Bash => Path::new("bash-completion/completions").join(stem),
+            CompletionShell::Zsh => match stem.starts_with('_') {
+                true => Path::new("zsh/site-functions").join(stem),
+                false => Path::new("zsh/site-functions").join(format!("_{stem}")),
+            },
+            CompletionShell::Fish => Path::new("fish/vendor_completions.d").join(format!("{stem}.fish")),
+        }
+    }
+}
+
+/// Extract the squashfs image of the AppImage into `squashfs-root` under the directory, then remove the AppImage.
+///
+/// The AppImage runtime extracts the image itself without FUSE, so no squashfs implementation is needed.
+fn extract_appimage_into(appimage: &Path, dir: &Path) -> anyhow::Result<()> {
+    let output = Command::new(appimage)
+        .arg("--appimage-extract")
+        .current_dir(dir)
+        .stdin(Stdio::null())
+        .output()?;
+    if !output.status.success() {
+        bail!("{}: {}", output.status, String::from_utf8_lossy(&output.stderr).trim());
+    }
+    if !dir.join("squashfs-root").is_dir() {
+        bail!("no squashfs-root extracted");
+    }
+    fs::remove_file(appimage)?;
+    Ok(())
+}
+
+/// Check whether the binary can run on the target platform by its ELF header, other files like scripts are skipped.
+fn check_binary(ctx: &Context, name: &str, path: &Path) -> anyhow::Result<()> {
+    let Some(elf) = Elf::read(path)? else {
+        return Ok(());
+    };
+    let target = &ctx.target;
+    let reason = match elf.arch() {
+        _ if target.os == "macos" => format!("is built for linux, not {}", target.os),
+        Some(arch) if arch != target.arch => format!("is built for {arch}, not {}", target.arch),
+        None => format!(
+            "is built for an unknown {}-bit machine type {}, not {}",
+            elf.bits, elf.machine, target.arch
+        ),
+        Some(_) => match &elf.interpreter {
+            Some(interpreter) if target.is_host() && !interpreter.exists() => {
+                format!("requires the missing dynamic loader {}", interpreter.display())
+            }
+            _ => return Ok(()),
+        },
+    };
+    Err(IncompatibleBinary(format!("binary '{name}' {reason}")).into())
+}
+
+/// Find the binary by its path relative to the install directory, or by its name anywhere in the install directory.
+fn find_binary(ctx: &Context, install_dir: &Path, bin: &str) -> anyhow::Result<Option<PathBuf>> {
+    if install_dir.join(bin).is_file() {
+        return Ok(Some(bin.into()));
+    }
+    if bin.contains('/') {
+        return Ok(None);
+    }
+
+    let mut bin_candidates = Vec::new();
+    for entry in WalkDir::new(install_dir)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_ok(|entry| entry.path().is_file())
+    {
+        let entry = entry?;
+
+        let path = entry.path().strip_prefix(install_dir)?;
+        // completions are often named after the binary too, like `bash-completion/completions/fd`
+        if entry.file_name() == OsStr::new(bin) && completion_shell(path).is_none() {
+            trace!("found bin candidate: {}", path.display());
+            bin_candidates.push(path.to_owned());
+        }
+    }
+
+    match &bin_candidates[..] {
+        [] => Ok(None),
+        [path, ..] => {
+            if bin_candidates.len() > 1 {
+                ctx.log_warning(
+                    "Warning",
+                    format!(
+                        "Multiple binaries found in archive, using the first one: '{}'",
+                        install_dir.join(path).shorten()?
+                    ),
+                );
+            }
+            Ok(Some(path.clone()))
+        }
+    }
+}
+
+/// Find the links in the bin and share directories which point into the data directory, returns the pairs of the link
+/// and its target.
+pub fn find_links(ctx: &Context) -> anyhow::Result<Vec<(PathBuf, PathBuf)>> {
+    let mut links = Vec::new();
+    for entry in WalkDir::new(&ctx.bin_dir)
+        .max_depth(1)
+        .into_iter()
+        .chain(WalkDir::new(&ctx.share_dir))
+    {
+        let entry = entry?;
+        if !entry.path_is_symlink() {
+            continue;
+        }
+        let target = fs::read_link(entry.path())?;
+        if target.starts_with(&ctx.data_dir) {
+            links.push((entry.into_path(), target));
+        }
+    }
+    Ok(links)
+}
+
+/// Remove the installed files of a package and the links it owns in the bin and share directories.
+pub fn uninstall_package(ctx: &Context, name: &str) -> anyhow::Result<()> {
+    let pkg_dir = ctx.data_dir.join(name);
+
+    for (path, target) in find_links(ctx)? {
+        if target.starts_with(&pkg_dir) {
+            fs::remove_file(&path)?;
+            debug!("link removed: '{}' -> '{}'", path.display(), target.display());
+        }
+    }
+
+    rm_rf(&pkg_dir)?;
+
+    Ok(())
+}
@@ -12,9 +12,9 @@ use std::process;
 use anyhow::Context as _;
 use clap::{CommandFactory as _, Parser as _, ValueEnum};
 use clap_complete::{generate, generate_to, Shell};
-use cli::{Opt, SubCommand, ENV_BIN_DIR, ENV_CACHE_DIR, ENV_CONFIG_DIR, ENV_DATA_DIR};
-use config::{Package, Source};
-use context::{log_error, Context};
+use cli::{Opt, SourceKind, SubCommand, ENV_BIN_DIR, ENV_CACHE_DIR, ENV_CONFIG_DIR, ENV_DATA_DIR, ENV_SHARE_DIR};
+use config::{Config, Package, Source};
+use context::{log_error, Context, Limits};
 use tracing_subscriber::EnvFilter;
 use util::{mkdir_p, Shorten as _, CRATE_NAME};
 
@@ -22,7 +22,18 @@ fn try_main() -> anyhow::Result<()> {
     let opt = Opt::parse();
     let output = opt.output_opt();
 
-    let Opt { bin_dir, data_dir, cache_dir, config_dir, command, .. } = opt;
+    let Opt {
+        bin_dir,
+        share_dir,
+        data_dir,
+        cache_dir,
+        config_dir,
+        max_extract_size,
+        max_extract_files,
+        target,
+        command,
+        ..
+    } = opt;
 
     let xdg_dirs = xdg::BaseDirectories::with_prefix(CRATE_NAME)?;
 
@@ -38,19 +49,35 @@ fn try_main() -> anyhow::Result<()> {
     let bin_dir = bin_dir.unwrap_or_else(|| xdg_dirs.get_data_home().join("bin"));
     mkdir_p(&bin_dir).context("failed to create binary dir")?;
 
+    let share_dir = share_dir.unwrap_or_else(|| xdg_dirs.get_data_home().join("share"));
+    mkdir_p(&share_dir).context("failed to create share dir")?;
+
     let config_file = config_dir.join("packages.toml");
     let lock_file = config_dir.join("packages.lock");
 
+    let defaults = Limits::default();
+    let limits = Limits {
+        size:  max_extract_size.unwrap_or(defaults.size),
+        files: max_extract_files.unwrap_or(defaults.files),
+    };
+
+    // the target configured in the config file is only loaded by the commands resolving assets
+    let configured_target = target.is_none();
+    let target = target.unwrap_or_default();
+
     let version = util::CRATE_RELEASE.to_string();
-    let ctx = Context {
+    let mut ctx = Context {
         version,
         config_file,
         config_dir,
         cache_dir,
         data_dir,
         bin_dir,
+        share_dir,
         lock_file,
         output,
+        limits,
+        target,
     };
 
     macro_rules! with_flock {
@@ -60,36 +87,61 @@ fn try_main() -> anyhow::Result<()> {
         };
     }
 
+    macro_rules! with_target {
+        ($command:expr) => {
+            // a missing config file is initialized by the command itself
+            if configured_target && ctx.config_file.exists() {
+                ctx.target = Config::load(&ctx)?.target.unwrap_or_default();
+            }
+            with_flock!($command);
+        };
+    }
+
     match command {
         SubCommand::Init { from } => {
             with_flock!(commands::init(&ctx, from)?);
         }
-        SubCommand::List => {
-            with_flock!(commands::list(&ctx)?);
+        SubCommand::List { all_versions } => {
+            with_flock!(commands::list(&ctx, all_versions)?);
         }
         SubCommand::Sync => {
-            with_flock!(commands::sync(&ctx)?);
+            with_target!(commands::sync(&ctx)?);
         }
         SubCommand::Update { package } => {
-            with_flock!(commands::update(&ctx, package)?);
+            with_target!(commands::update(&ctx, package)?);
         }
         SubCommand::Restore { package } => {
-            with_flock!(commands::restore(&ctx, package)?);
+            with_target!(commands::restore(&ctx, package)?);
+        }
+        SubCommand::Outdated => {
+            with_target!(if commands::outdated(&ctx)? {
+                // distinct from the code of errors
+                process::exit(2);
+            });
         }
         SubCommand::Find { query, top } => {
             with_flock!(commands::find(query, top, &ctx)?);
         }
-        SubCommand::Add { name, repo: (owner, repo), version, desc } => {
-            let pkg = Package {
-                name: name.unwrap_or_else(|| repo.clone()),
-                source: Source::Github { repo: format!("{}/{}", owner, repo) },
-                version,
-                desc,
+        SubCommand::Add { name, repo: (owner, repo), source, host, version, desc } => {
+            // Nested groups are allowed by some sources, use the last component as the default name.
+            let name = name.unwrap_or_else(|| repo.rsplit('/').next().unwrap_or(&repo).to_owned());
+            let repo = format!("{}/{}", owner, repo);
+            let source = match source {
+                SourceKind::Github => Source::Github { repo, host, api: None },
+                SourceKind::Gitlab => Source::Gitlab { repo, host },
+                SourceKind::Gitea => Source::Gitea { repo, host },
             };
-            with_flock!(commands::add(&ctx, pkg)?);
+            let pkg = Package { name, source, version, desc, ..Default::default() };
+            with_target!(commands::add(&ctx, pkg)?);
+        }
+        SubCommand::Remove { packages, cache } => {
+            with_flock!(commands::remove(&ctx, packages, cache)?);
+        }
+        SubCommand::Use { package: (name, version) } => {
+            with_flock!(commands::use_version(&ctx, &name, &version)?);
         }
-        SubCommand::Cleanup { cache } => {
-            with_flock!(commands::cleanup(&ctx, cache)?);
+        SubCommand::Cleanup { cache, keep } => {
+            with_flock!(commands::cleanup(&ctx, cache, keep)?);
         }
 
         SubCommand::Env => {
@@ -102,7 +154,16 @@ fn try_main() -> anyhow::Result<()> {
             print_env!(ENV_CACHE_DIR, ctx.cache_dir);
             print_env!(ENV_DATA_DIR, ctx.data_dir);
             print_env!(ENV_BIN_DIR, ctx.bin_dir);
+            print_env!(ENV_SHARE_DIR, ctx.share_dir);
             println!(r#"export PATH="${ENV_BIN_DIR}:$PATH""#);
+            // the trailing colon keeps the default search path of man
+            println!(r#"export MANPATH="${ENV_SHARE_DIR}/man:$MANPATH""#);
+            // bash-completion and fish look for completions in the data dirs
+            println!(r#"export XDG_DATA_DIRS="${ENV_SHARE_DIR}:${{XDG_DATA_DIRS:-/usr/local/share:/usr/share}}""#);
+            // `fpath` is only available in zsh, eval it to keep other shells parsing
+            println!(
+                r#"if [ -n "$ZSH_VERSION" ]; then eval 'fpath=("${ENV_SHARE_DIR}/zsh/site-functions" $fpath)'; fi"#
+            );
         }
         SubCommand::Completions { shell, dir, list } => {
             let cmd = &mut Opt::command();
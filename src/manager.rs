@@ -1,29 +1,58 @@
-use anyhow::Result;
+use anyhow::{bail, Context as _, Result};
+use itertools::Itertools;
 use rayon::iter::{IntoParallelIterator, IntoParallelRefIterator, ParallelIterator};
+use semver::VersionReq;
 
 use crate::{
-    config::{Config, LockedConfig, LockedPackage, Package},
+    config::{Bins, Config, LockedConfig, LockedPackage, Package},
     context::Context,
-    installer::install_package,
-    provider::{Github, Provider},
+    installer::{install_package, IncompatibleBinary},
+    provider::{self, Provider},
+    util::{
+        remove_file_if_exists,
+        verify_sha256,
+        version::{parse_constraint, parse_tag},
+    },
 };
 
 pub fn sync_package(ctx: &Context, pkg: &Package, lpkg: Option<&LockedPackage>, update: bool) -> Result<LockedPackage> {
-    match (&pkg.version, lpkg) {
+    // The package has to be installed again if the files to link are changed.
+    let installed = lpkg.filter(|lpkg| same_files(ctx, pkg, lpkg));
+    let req = pkg.version.as_deref().map(parse_constraint).transpose()?.flatten();
+    match (&pkg.version, installed) {
         // If the package is already installed and the version matches, do nothing.
         (Some(version), Some(lpkg)) if version == &lpkg.version => {
             ctx.log_status("Checked", format!("{}@{}", pkg.name, lpkg.version));
             Ok(lpkg.clone())
         }
+        // If the locked version still satisfies the constraint, keep it until an explicit update.
+        (Some(_), Some(lpkg)) if !update && req.as_ref().is_some_and(|req| satisfies(req, &lpkg.version)) => {
+            ctx.log_status("Checked", format!("{}@{}", pkg.name, lpkg.version));
+            Ok(lpkg.clone())
+        }
         (None, Some(lpkg)) if !update => {
             ctx.log_status("Checked", format!("{}@{}", pkg.name, lpkg.version));
             Ok(lpkg.clone())
         }
         _ => {
-            let provider = Github::new(ctx.clone())?;
+            let provider = provider::new(&pkg.source)?;
             let new = provider.download(ctx, pkg)?;
-
-            install_package(ctx, &new)?;
+            let new = match ctx.target.is_host() {
+                true => install_package(ctx, &new, Some(pkg))
+                    .map_err(|e| suggest_assets(ctx, provider.as_ref(), &new, e))?,
+                // nothing is installed for another platform, so the declared files are locked as is
+                false => LockedPackage {
+                    bins: pkg
+                        .bins
+                        .iter()
+                        .flat_map(Bins::entries)
+                        .map(|(name, bin)| (name.to_owned(), bin.to_owned()))
+                        .collect(),
+                    completions: pkg.completions.clone(),
+                    manpages: pkg.manpages.clone(),
+                    ..new
+                },
+            };
 
             match lpkg {
                 Some(old) if old != &new => {
@@ -38,12 +67,96 @@ pub fn sync_package(ctx: &Context, pkg: &Package, lpkg: Option<&LockedPackage>,
     }
 }
 
+/// Suggest the other assets of the release if the installed one turns out not to run on the target platform.
+fn suggest_assets(ctx: &Context, provider: &dyn Provider, lpkg: &LockedPackage, e: anyhow::Error) -> anyhow::Error {
+    if !e.is::<IncompatibleBinary>() {
+        return e;
+    }
+    let others = match provider.list_assets(lpkg) {
+        Ok(assets) => assets.into_iter().filter(|name| *name != lpkg.filename).collect_vec(),
+        Err(err) => {
+            ctx.log_verbose_warning("Warning", format!("failed to list the assets of {}: {err}", lpkg.name));
+            Vec::new()
+        }
+    };
+    let msg = format!("{} doesn't run on {}", lpkg.filename, ctx.target);
+    match others.is_empty() {
+        true => e.context(msg),
+        false => e.context(format!(
+            "{msg}, specify `asset` to choose one of the others:\n  {}",
+            others.join("\n  ")
+        )),
+    }
+}
+
+/// Check whether the locked package links the same files as the package declares.
+fn same_files(ctx: &Context, pkg: &Package, lpkg: &LockedPackage) -> bool {
+    let mut names = match &pkg.bins {
+        Some(bins) => bins.entries().into_iter().map(|(name, _)| name).collect(),
+        None => vec![pkg.name.as_str()],
+    };
+    names.sort_unstable();
+
+    // the detected files are not declared
+    let same = |declared: &Option<Vec<String>>, locked: &Option<Vec<String>>| match (declared, locked) {
+        (Some(declared), Some(locked)) => declared.iter().sorted().eq(locked.iter().sorted()),
+        (Some(_), None) => false,
+        (None, _) => true,
+    };
+
+    names == lpkg.bin_names()
+        && same(&pkg.completions, &lpkg.completions)
+        && same(&pkg.manpages, &lpkg.manpages)
+        && pkg.extract_appimage == lpkg.extract_appimage
+        && pkg.locked_targets(&ctx.target).iter().eq(lpkg.targets.keys())
+}
+
+/// Check whether the locked tag satisfies the version constraint.
+fn satisfies(req: &VersionReq, tag: &str) -> bool {
+    parse_tag(tag).is_some_and(|version| req.matches(&version))
+}
+
 pub fn restore_package(ctx: &Context, lpkg: &LockedPackage) -> Result<()> {
-    let provider = Github::new(ctx.clone())?;
+    if lpkg.filename.is_empty() {
+        bail!(
+            "no asset of {}@{} is locked for {}",
+            lpkg.name,
+            lpkg.version,
+            ctx.target
+        );
+    }
+
+    let provider = provider::new(&lpkg.source)?;
+
+    let path = ctx.cache_dir.join(&lpkg.filename);
+
+    // discard the cached asset if it is corrupted, so that it will be downloaded again
+    if let Some(sha256) = &lpkg.sha256 {
+        if path.exists() && verify_sha256(&path, sha256).is_err() {
+            ctx.log_warning(
+                "Corrupted",
+                format!("Cached asset will be downloaded again: {}", lpkg.filename),
+            );
+            remove_file_if_exists(&path)?;
+        }
+    }
 
     provider.download_locked(ctx, lpkg)?;
 
-    install_package(ctx, lpkg)?;
+    // refuse to install an asset which differs from the locked one
+    if let Some(sha256) = &lpkg.sha256 {
+        verify_sha256(&path, sha256).with_context(|| {
+            format!(
+                "failed to restore {}@{}, the remote asset has changed since locked",
+                lpkg.name, lpkg.version
+            )
+        })?;
+    }
+
+    // the asset of another platform is only downloaded, e.g. to be bundled for offline use
+    if ctx.target.is_host() {
+        install_package(ctx, lpkg, None)?;
+    }
     ctx.log_status("Checked", format!("{}@{}", lpkg.name, lpkg.version));
 
     Ok(())
@@ -66,9 +179,7 @@ pub fn sync_packages(ctx: &Context, cfg: &Config, lcfg: &mut LockedConfig) -> Re
 
 /// Restore packages according to the given [`LockedConfig`].
 pub fn restore_packages(lcfg: LockedConfig) -> Result<()> {
-    lcfg.pkgs.into_par_iter().for_each(|(_, pkg)| {
-        restore_package(&lcfg.ctx, &pkg).unwrap();
-    });
-
-    Ok(())
+    lcfg.pkgs
+        .into_par_iter()
+        .try_for_each(|(_, pkg)| restore_package(&lcfg.ctx, &pkg))
 }
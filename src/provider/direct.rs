@@ -0,0 +1,149 @@
+use anyhow::{bail, Context as _, Result};
+use ureq::Agent;
+use url::Url;
+
+use crate::{
+    config::{LockedAsset, LockedPackage, Package, Source},
+    context::Context,
+    util::{sha256_file, target::Target, verify_sha256, version::parse_constraint},
+};
+
+use super::{download_asset, lock_targets, verify_signature, Provider};
+
+/// Provider for packages downloaded from a direct URL.
+pub struct Direct {
+    client: Agent,
+}
+
+impl Direct {
+    pub fn new() -> Result<Self> {
+        let agent = ureq::AgentBuilder::new().user_agent("rpk").build();
+        Ok(Direct { client: agent })
+    }
+
+    /// Download the asset of the target platform into the cache directory and verify it, returns the asset to lock.
+    ///
+    /// The asset is cached by the package name, version and platform, as the same filename is often reused across
+    /// versions and platforms. Without a published checksum, a cached asset is only reused if its signature verifies.
+    fn fetch(&self, ctx: &Context, pkg: &Package, version: &str, target: &Target) -> Result<LockedAsset> {
+        let Source::Url { url } = &pkg.source else {
+            bail!("Unsupported source: `{}`", pkg.source);
+        };
+        let download_url = expand_url(url, version, target)?;
+        let filename = format!("{}/{version}/{target}/{}", pkg.name, asset_name(&download_url)?);
+
+        let path = ctx.cache_dir.join(&filename);
+
+        let cached = pkg.verify.is_some() && path.exists();
+        if cached {
+            ctx.log_verbose_status("Skipped", format!("Asset already exists: {}", filename));
+        } else {
+            download_asset(&self.client, ctx, &filename, download_url.clone())?;
+        }
+
+        if let Some(verify) = &pkg.verify {
+            let mut url = download_url.clone();
+            url.set_path(&format!("{}{}", download_url.path(), verify.suffix()));
+            match verify_signature(&self.client, ctx, verify, &filename, url.clone()) {
+                Err(e) if cached => {
+                    ctx.log_warning("Unverified", format!("Cached asset will be downloaded again: {e:#}"));
+                    download_asset(&self.client, ctx, &filename, download_url.clone())?;
+                    verify_signature(&self.client, ctx, verify, &filename, url)?;
+                }
+                result => result?,
+            }
+        }
+
+        Ok(LockedAsset {
+            filename,
+            download_url: download_url.into(),
+            sha256: sha256_file(&path)?.into(),
+        })
+    }
+}
+
+/// Expand the placeholders in the URL template for the target platform.
+fn expand_url(template: &str, version: &str, target: &Target) -> Result<Url> {
+    let url = template
+        .replace("{version}", version)
+        .replace("{os}", &target.os)
+        .replace("{arch}", &target.arch)
+        .replace("{target}", &target.triple());
+    url.parse().context(format!("Invalid url: `{url}`"))
+}
+
+/// Get the filename of the asset from the last segment of the URL path.
+fn asset_name(url: &Url) -> Result<String> {
+    url.path_segments()
+        .and_then(|segments| segments.last())
+        .filter(|name| !name.is_empty())
+        .map(ToOwned::to_owned)
+        .context(format!("Cannot determine the filename from url: `{url}`"))
+}
+
+impl Provider for Direct {
+    fn resolve_version(&self, pkg: &Package) -> Result<String> {
+        let version = pkg
+            .version
+            .clone()
+            .context(format!("version is required for url source: `{}`", pkg.name))?;
+        // there are no releases to match a constraint against
+        if parse_constraint(&version)?.is_some() {
+            bail!(
+                "version constraint is not supported for url source: `{}@{version}`",
+                pkg.name
+            );
+        }
+        Ok(version)
+    }
+
+    fn download(&self, ctx: &Context, pkg: &Package) -> Result<LockedPackage> {
+        let version = self.resolve_version(pkg)?;
+
+        let asset = self.fetch(ctx, pkg, &version, &ctx.target)?;
+        let targets = lock_targets(ctx, pkg, &asset, |target| self.fetch(ctx, pkg, &version, target))?;
+
+        Ok(LockedPackage {
+            name: pkg.name.clone(),
+            version,
+            source: pkg.source.clone(),
+            desc: pkg.desc.clone(),
+            filename: asset.filename,
+            download_url: asset.download_url,
+            sha256: asset.sha256,
+            bins: Default::default(),
+            completions: None,
+            manpages: None,
+            extract_appimage: pkg.extract_appimage,
+            targets,
+        })
+    }
+
+    fn download_locked(&self, ctx: &Context, lpkg: &LockedPackage) -> Result<()> {
+        let path = ctx.cache_dir.join(&lpkg.filename);
+
+        // skip download if the asset already exists and matches the lock
+        if path.exists()
+            && lpkg
+                .sha256
+                .as_ref()
+                .is_some_and(|sha256| verify_sha256(&path, sha256).is_ok())
+        {
+            ctx.log_verbose_status("Skipped", format!("Asset already exists: {}", lpkg.filename));
+            return Ok(());
+        }
+
+        let download_url = match (&lpkg.download_url, &lpkg.source) {
+            (Some(url), _) => url.clone(),
+            (None, Source::Url { url }) => expand_url(url, &lpkg.version, &ctx.target)?,
+            (None, source) => bail!("Unsupported source: `{}`", source),
+        };
+
+        download_asset(&self.client, ctx, &lpkg.filename, download_url)
+    }
+
+    fn list_assets(&self, _pkg: &LockedPackage) -> Result<Vec<String>> {
+        // there are no releases listing the assets
+        Ok(Vec::new())
+    }
+}
@@ -0,0 +1,173 @@
+use anyhow::{anyhow, Context as _, Result};
+use models::{Release, Repository};
+use ureq::Agent;
+use url::Url;
+
+use super::{github::models as gh, list_pages, new_client, Forge};
+
+const GITEA_HOST: &str = "https://codeberg.org";
+
+/// Provider for Gitea and its derivatives like Forgejo and Codeberg.
+pub struct Gitea {
+    client: Agent,
+
+    /// The base URL of the Gitea instance.
+    host: Url,
+}
+
+impl Gitea {
+    pub fn new(host: Option<Url>) -> Result<Self> {
+        let host = match host {
+            Some(host) => host,
+            None => GITEA_HOST.parse()?,
+        };
+
+        let agent = new_client("GITEA_TOKEN", &host);
+
+        Ok(Gitea { client: agent, host })
+    }
+
+    /// Build the API endpoint of the given repository.
+    fn endpoint(&self, repo: &str, segments: &[&str]) -> Result<Url> {
+        let (owner, name) = repo.split_once('/').context(format!("Invalid repo: `{repo}`"))?;
+        let mut url = self.host.clone();
+        url.path_segments_mut()
+            .map_err(|_| anyhow!("Invalid Gitea host: `{}`", self.host))?
+            .pop_if_empty()
+            .extend(["api", "v1", "repos", owner, name])
+            .extend(segments);
+        Ok(url)
+    }
+
+    pub fn get_release(&self, repo: &str, version: Option<&str>) -> Result<Release> {
+        let url = match version {
+            Some(version) => self.endpoint(repo, &["releases", "tags", version])?,
+            None => self.endpoint(repo, &["releases", "latest"])?,
+        };
+        self.client
+            .get(url.as_str())
+            .call()
+            .context(format!(
+                "failed to get release: `{repo}@{version}`",
+                version = version.unwrap_or("latest")
+            ))?
+            .into_json()
+            .map_err(Into::into)
+    }
+
+    pub fn list_releases(&self, repo: &str) -> Result<Vec<Release>> {
+        let url = self.endpoint(repo, &["releases"])?;
+
+        list_pages(&self.client, &url, "limit").context(format!("failed to list releases: `{repo}`"))
+    }
+
+    pub fn get_repo(&self, repo: &str) -> Result<Repository> {
+        let url = self.endpoint(repo, &[])?;
+        self.client
+            .get(url.as_str())
+            .call()
+            .context(format!("failed to get repo: `{repo}`"))?
+            .into_json()
+            .map_err(Into::into)
+    }
+}
+
+impl Forge for Gitea {
+    fn client(&self) -> &Agent {
+        &self.client
+    }
+
+    fn get_release(&self, repo: &str, version: Option<&str>) -> Result<gh::Release> {
+        self.get_release(repo, version).map(Release::into_github)
+    }
+
+    fn list_releases(&self, repo: &str) -> Result<Vec<gh::Release>> {
+        let releases = self.list_releases(repo)?;
+        Ok(releases.into_iter().map(Release::into_github).collect())
+    }
+
+    fn get_description(&self, repo: &str) -> Option<String> {
+        self.get_repo(repo)
+            .ok()
+            .and_then(|repo| repo.description)
+            .filter(|desc| !desc.is_empty())
+    }
+}
+
+mod models {
+    use serde::Deserialize;
+    use url::Url;
+
+    use crate::provider::github::models as gh;
+
+    #[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
+    pub struct Repository {
+        pub id:             u64,
+        pub name:           String,
+        pub full_name:      String,
+        pub description:    Option<String>,
+        pub default_branch: Option<String>,
+        pub html_url:       Option<Url>,
+    }
+
+    #[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
+    pub struct Release {
+        pub name:             Option<String>,
+        pub body:             Option<String>,
+        pub tag_name:         String,
+        pub target_commitish: Option<String>,
+        pub tarball_url:      Option<Url>,
+        pub zipball_url:      Option<Url>,
+        pub draft:            bool,
+        pub prerelease:       bool,
+        pub created_at:       Option<String>,
+        pub published_at:     Option<String>,
+        pub assets:           Vec<Asset>,
+    }
+
+    #[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
+    pub struct Asset {
+        pub id:                   u64,
+        pub name:                 String,
+        pub size:                 i64,
+        pub download_count:       i64,
+        pub created_at:           String,
+        pub browser_download_url: Url,
+    }
+
+    impl Release {
+        /// Map the release onto the GitHub release model.
+        pub fn into_github(self) -> gh::Release {
+            gh::Release {
+                name:             self.name,
+                body:             self.body,
+                tag_name:         self.tag_name,
+                target_commitish: self.target_commitish.unwrap_or_default(),
+                tarball_url:      self.tarball_url,
+                zipball_url:      self.zipball_url,
+                draft:            self.draft,
+                prerelease:       self.prerelease,
+                created_at:       self.created_at,
+                published_at:     self.published_at,
+                assets:           self.assets.into_iter().map(Asset::into_github).collect(),
+            }
+        }
+    }
+
+    impl Asset {
+        pub fn into_github(self) -> gh::Asset {
+            gh::Asset {
+                name:                 self.name,
+                url:                  self.browser_download_url.clone(),
+                browser_download_url: self.browser_download_url,
+                label:                None,
+                state:                "uploaded".to_owned(),
+                content_type:         Default::default(),
+                size:                 self.size,
+                download_count:       self.download_count,
+                updated_at:           self.created_at.clone(),
+                created_at:           self.created_at,
+            }
+        }
+    }
+}
@@ -1,47 +1,65 @@
-use std::{
-    cmp::Reverse,
-    env::{
-        self,
-        consts::{ARCH, OS},
-    },
-};
+use std::cmp::Reverse;
 
-use anyhow::{anyhow, Context as _, Result};
+use anyhow::{anyhow, bail, Context as _, Result};
+use itertools::Itertools;
 use models::{Asset, Release, RepoSearchResult, Repository};
 use tracing::{debug, trace, warn};
 use ureq::Agent;
 use url::Url;
 
-use crate::{
-    config::{LockedPackage, Package, Source},
-    context::Context,
-    util::http::{BearerAuthMiddleware, UreqExt as _},
+use crate::util::{
+    pattern::Pattern,
+    target::{Libc, Target},
 };
 
-use super::Provider;
+use super::{list_pages, new_client, Forge};
+
+const GITHUB_HOST: &str = "github.com";
+const GITHUB_API: &str = "https://api.github.com";
 
 pub struct Github {
     client: Agent,
 
-    ctx: Context,
+    /// The base URL of the GitHub API.
+    api: Url,
 }
 
 impl Github {
-    pub fn new(ctx: Context) -> Result<Self> {
-        let token = env::var("GITHUB_TOKEN").or_else(|_| env::var("RPK_GITHUB_TOKEN")).ok();
+    /// Create a GitHub client. A GitHub Enterprise Server can be specified by its web host, whose API is served under
+    /// `/api/v3`, or by the API base URL directly.
+    pub fn new(host: Option<&Url>, api: Option<&Url>) -> Result<Self> {
+        let api = match (host, api) {
+            (_, Some(api)) => api.clone(),
+            (Some(host), None) if host.host_str() != Some(GITHUB_HOST) => {
+                let mut api = host.clone();
+                api.path_segments_mut()
+                    .map_err(|_| anyhow!("Invalid GitHub host: `{host}`"))?
+                    .pop_if_empty()
+                    .extend(["api", "v3"]);
+                api
+            }
+            _ => GITHUB_API.parse()?,
+        };
+
+        let agent = new_client("GITHUB_TOKEN", &api);
 
-        let agent = ureq::AgentBuilder::new()
-            .user_agent("rpk")
-            .middleware(BearerAuthMiddleware(token))
-            .build();
+        Ok(Github { client: agent, api })
+    }
 
-        Ok(Github { client: agent, ctx })
+    /// Build the API endpoint from the given path segments.
+    fn endpoint<'a>(&self, segments: impl IntoIterator<Item = &'a str>) -> Result<Url> {
+        let mut url = self.api.clone();
+        url.path_segments_mut()
+            .map_err(|_| anyhow!("Invalid GitHub API: `{}`", self.api))?
+            .pop_if_empty()
+            .extend(segments);
+        Ok(url)
     }
 
     pub fn search_repo(&self, query: &str, size: impl Into<u8>) -> Result<Vec<Repository>> {
         let res: RepoSearchResult = self
             .client
-            .get("https://api.github.com/search/repositories")
+            .get(self.endpoint(["search", "repositories"])?.as_str())
             .query("q", query)
             .query("per_page", &size.into().to_string())
             .call()
@@ -52,14 +70,18 @@ impl Github {
     }
 
     pub fn get_release(&self, repo: &str, version: Option<&str>) -> Result<Release> {
+        let (owner, name) = self.parse_repo(repo)?;
         match version {
             Some(version) => self
                 .client
-                .get(&format!("https://api.github.com/repos/{repo}/releases/tags/{version}",))
+                .get(
+                    self.endpoint(["repos", owner, name, "releases", "tags", version])?
+                        .as_str(),
+                )
                 .call(),
             None => self
                 .client
-                .get(&format!("https://api.github.com/repos/{repo}/releases/latest"))
+                .get(self.endpoint(["repos", owner, name, "releases", "latest"])?.as_str())
                 .call(),
         }
         .context(format!(
@@ -70,9 +92,17 @@ impl Github {
         .map_err(Into::into)
     }
 
+    pub fn list_releases(&self, repo: &str) -> Result<Vec<Release>> {
+        let (owner, name) = self.parse_repo(repo)?;
+        let url = self.endpoint(["repos", owner, name, "releases"])?;
+
+        list_pages(&self.client, &url, "per_page").context(format!("failed to list releases: `{repo}`"))
+    }
+
     pub fn get_repo(&self, repo: &str) -> Result<Repository> {
+        let (owner, name) = self.parse_repo(repo)?;
         self.client
-            .get(&format!("https://api.github.com/repos/{}", repo))
+            .get(self.endpoint(["repos", owner, name])?.as_str())
             .call()
             .context(format!("failed to get repo: `{repo}`"))?
             .into_json()
@@ -82,87 +112,23 @@ impl Github {
     pub fn parse_repo<'a>(&self, repo: &'a str) -> Result<(&'a str, &'a str)> {
         repo.split_once('/').context(format!("Invalid repo: `{repo}`"))
     }
-
-    pub fn download_asset(&self, name: &str, url: Url) -> Result<()> {
-        self.ctx.log_verbose_status("Downloading", &url);
-        self.client
-            .download(url, self.ctx.cache_dir.join(name))
-            .context("failed to download asset")?;
-        self.ctx.log_status("Downloaded", name);
-        Ok(())
-    }
 }
 
-impl Provider for Github {
-    fn download(&self, ctx: &Context, pkg: &Package) -> Result<LockedPackage> {
-        let repo = match &pkg.source {
-            Source::Github { repo } => repo,
-        };
-
-        let release = self.get_release(repo, pkg.version.as_deref())?;
-        ctx.log_verbose_status("Fetched", format!("{repo}@{version}", version = release.tag_name));
-
-        let asset = filter_assets(&release)?;
-        let asset = asset.ok_or_else(|| anyhow!("No matching asset found for {repo}@{}", release.tag_name))?;
-        ctx.log_verbose_status("Filtered", &asset.name);
-
-        let path = ctx.cache_dir.join(&asset.name);
-
-        // skip download if the asset already exists
-        if path.exists() {
-            ctx.log_verbose_status("Skipped", format!("Asset already exists: {}", asset.name));
-        } else {
-            self.download_asset(&asset.name, asset.browser_download_url.clone())?;
-        }
-
-        // get description from the release if not provided
-        let desc = match &pkg.desc {
-            Some(desc) => desc.clone().into(),
-            None => self.get_repo(repo).ok().and_then(|repo| repo.description),
-        };
-
-        Ok(LockedPackage {
-            name:         pkg.name.clone(),
-            version:      release.tag_name.clone(),
-            source:       pkg.source.clone(),
-            desc:         desc.map(|desc| desc.trim().to_string()),
-            filename:     asset.name.clone(),
-            download_url: asset.browser_download_url.clone().into(),
-        })
+impl Forge for Github {
+    fn client(&self) -> &Agent {
+        &self.client
     }
 
-    fn download_locked(&self, ctx: &Context, lpkg: &LockedPackage) -> Result<()> {
-        let path = ctx.cache_dir.join(&lpkg.filename);
-
-        // skip download if the asset already exists
-        if path.exists() {
-            ctx.log_verbose_status("Skipped", format!("Asset already exists: {}", lpkg.filename));
-            return Ok(());
-        }
-
-        let repo = match &lpkg.source {
-            Source::Github { repo } => repo,
-        };
-        let version = &lpkg.version;
-
-        let download_url = match lpkg.download_url.as_ref() {
-            Some(url) => url.clone(),
-            None => {
-                let (owner, repo) = repo.split_once('/').ok_or_else(|| anyhow::anyhow!("Invalid repo"))?;
-                let release = self.get_release(repo, Some(version))?;
-                ctx.log_verbose_status("Fetched", format!("{owner}/{repo}@{version}"));
-                let asset = release
-                    .assets
-                    .iter()
-                    .find(|asset| asset.name == lpkg.filename)
-                    .ok_or_else(|| anyhow::anyhow!("Asset not found"))?;
-                asset.browser_download_url.clone()
-            }
-        };
+    fn get_release(&self, repo: &str, version: Option<&str>) -> Result<Release> {
+        self.get_release(repo, version)
+    }
 
-        self.download_asset(&lpkg.filename, download_url)?;
+    fn list_releases(&self, repo: &str) -> Result<Vec<Release>> {
+        self.list_releases(repo)
+    }
 
-        Ok(())
+    fn get_description(&self, repo: &str) -> Option<String> {
+        self.get_repo(repo).ok().and_then(|repo| repo.description)
     }
 }
 
@@ -184,66 +150,144 @@ macro_rules! ends_with_any {
     }};
 }
 
-fn filter_assets(release: &Release) -> anyhow::Result<Option<&Asset>> {
-    debug!("OS: {OS}, ARCH: {ARCH}");
-
-    let mut assets = release
-        .assets
-        .iter()
-        .inspect(|asset| {
-            trace!("before filter: {asset}", asset = asset.name);
-        })
-        .filter(|asset| match OS {
-            "linux" => is_linux(&asset.name),
-            "macos" => is_macos(&asset.name),
-            _ => {
-                warn!("unsupported OS: {OS}", OS = OS);
-                false
-            }
-        })
-        .filter(|asset| match ARCH {
-            "x86_64" => is_x86_64(&asset.name),
-            "x86" => is_x86(&asset.name),
-            // apple silicon macs can run x86_64 binaries
-            "aarch64" => is_aarch64(&asset.name) || is_macos(&asset.name) && is_x86_64(&asset.name),
-            "arm" => is_arm(&asset.name),
-            _ => {
-                warn!("unsupported ARCH: {ARCH}", ARCH = ARCH);
-                false
-            }
-        })
-        .filter(|asset| {
-            ends_with_any!(
-                asset.name,
-                ".sig",
-                ".deb",
-                ".rpm",
-                ".dmg",
-                ".apk",
-                ".msi",
-                ".sbom",
-                ".checksum",
-                ".sha256sum"
-            )
-        })
-        .collect::<Vec<_>>();
+/// Select the asset to install from the release.
+///
+/// An asset pattern bypasses the platform heuristics. Either way, the candidates are ranked by their priority, and it's
+/// an error if the best ones are tied.
+pub(super) fn filter_assets<'a>(
+    release: &'a Release,
+    include: Option<&Pattern>,
+    exclude: &[Pattern],
+    target: &Target,
+) -> Result<Option<&'a Asset>> {
+    debug!("OS: {}, ARCH: {}, LIBC: {:?}", target.os, target.arch, target.libc);
+
+    let candidates = |is_installable: &dyn Fn(&str) -> bool| {
+        release
+            .assets
+            .iter()
+            .inspect(|asset| {
+                trace!("before filter: {asset}", asset = asset.name);
+            })
+            .filter(|asset| !exclude.iter().any(|pattern| pattern.is_match(&asset.name)))
+            .filter(|asset| match include {
+                Some(pattern) => pattern.is_match(&asset.name),
+                None => is_installable(&asset.name),
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let mut assets = candidates(&|name| is_installable(name, target));
+    // fall back to the linux packages only if no other asset matches, preferring debian packages
+    for is_package in [is_deb, is_rpm] {
+        if assets.is_empty() && include.is_none() {
+            assets = candidates(&|name| is_package(name, target));
+        }
+    }
 
-    assets.sort_by_key(|asset| Reverse(priority(asset)));
+    assets.sort_by_key(|asset| Reverse(priority(asset, target)));
 
     match &assets[..] {
         [] => Ok(None),
         [asset] => Ok(Some(asset)),
-        [asset, ..] => {
-            warn!(
-                "{} assets found, the first one will be used: {:?}",
+        [asset, next, ..] if priority(asset, target) > priority(next, target) => {
+            debug!(
+                "{} assets found, the preferred one will be used: {:?}",
                 assets.len(),
                 assets.iter().map(|asset| &asset.name).collect::<Vec<_>>()
             );
             Ok(Some(asset))
         }
+        _ => {
+            let candidates = assets.iter().map(|asset| &asset.name).join("\n  ");
+            bail!("Ambiguous assets, specify `asset` or `exclude` to choose one of them:\n  {candidates}")
+        }
     }
 }
 
+/// Check if the asset is an installable one for the target platform.
+fn is_installable(name: &str, target: &Target) -> bool {
+    is_target_os(name, target)
+        && is_target_arch(name, target)
+        // signatures, checksums and system packages are excluded, they're not archives or binaries
+        && !is_metadata(name)
+        && !ends_with_any!(name, ".deb", ".rpm", ".dmg", ".apk", ".msi")
+}
+
+/// Check if the asset describes other assets, like their signatures or checksums.
+pub(super) fn is_metadata(name: &str) -> bool {
+    ends_with_any!(
+        name,
+        ".sig",
+        ".asc",
+        ".pem",
+        ".crt",
+        ".minisig",
+        ".sbom",
+        ".json",
+        ".txt",
+        ".checksum",
+        ".sha256",
+        ".sha256sum",
+        ".sha512",
+        ".sha512sum",
+        ".zsync"
+    )
+}
+
+/// Check if the asset is a Debian package for the target platform, whose OS is implied.
+fn is_deb(name: &str, target: &Target) -> bool {
+    target.os == "linux" && is_target_arch(name, target) && ends_with_any!(name, ".deb")
+}
+
+/// Check if the asset is an RPM package for the target platform, whose OS is implied.
+fn is_rpm(name: &str, target: &Target) -> bool {
+    target.os == "linux" && is_target_arch(name, target) && ends_with_any!(name, ".rpm")
+}
+
+fn is_target_os(name: &str, target: &Target) -> bool {
+    match target.os.as_str() {
+        // appimages are linux only, so the OS is often omitted from their names
+        "linux" => is_linux(name) || is_appimage(name),
+        "macos" => is_macos(name),
+        os => {
+            warn!("unsupported OS: {os}");
+            false
+        }
+    }
+}
+
+fn is_target_arch(name: &str, target: &Target) -> bool {
+    match target.arch.as_str() {
+        "x86_64" => is_x86_64(name),
+        "x86" => is_x86(name),
+        // apple silicon macs can run x86_64 binaries
+        "aarch64" => is_aarch64(name) || is_macos(name) && is_x86_64(name),
+        "arm" => is_arm(name),
+        arch => {
+            warn!("unsupported ARCH: {arch}");
+            false
+        }
+    }
+}
+
+/// Find the asset holding the checksum of the given asset, preferring a dedicated one over a combined one.
+pub(super) fn find_checksum<'a>(release: &'a Release, asset: &Asset) -> Option<&'a Asset> {
+    let name = asset.name.to_lowercase();
+    let dedicated = [".sha256", ".sha256sum", ".sha256.txt"].map(|ext| format!("{name}{ext}"));
+
+    release
+        .assets
+        .iter()
+        .find(|checksum| dedicated.contains(&checksum.name.to_lowercase()))
+        .or_else(|| {
+            release.assets.iter().find(|checksum| {
+                contains_any!(checksum.name, "checksums", "sha256sums")
+                    && !ends_with_any!(checksum.name, ".sig", ".asc", ".pem", ".minisig")
+            })
+        })
+}
+
 fn is_linux(filename: &str) -> bool {
     contains_any!(filename, "linux")
 }
@@ -272,12 +316,33 @@ fn is_musl(filename: &str) -> bool {
     contains_any!(filename, "musl")
 }
 
-fn priority(asset: &Asset) -> u64 {
+fn is_appimage(filename: &str) -> bool {
+    ends_with_any!(filename, ".appimage")
+}
+
+// check if the asset is in a format rpk can unpack
+fn is_unpackable(filename: &str) -> bool {
+    ends_with_any!(filename, ".tar", ".gz", ".tgz", ".xz", ".txz", ".bz2", ".tbz", ".tbz2", ".zst", ".tzst", ".zip")
+}
+
+fn priority(asset: &Asset, target: &Target) -> u64 {
     let mut priority = 0;
 
-    // choose the musl version if available
+    // choose the format which can be unpacked if available
+    priority <<= 1;
+    if is_unpackable(&asset.name) {
+        priority += 1;
+    }
+
+    // choose the self-contained appimage over other single files
     priority <<= 1;
-    if is_musl(&asset.name) {
+    if is_appimage(&asset.name) {
+        priority += 1;
+    }
+
+    // choose the version of the target libc, or the musl one if not specified since it runs on both
+    priority <<= 1;
+    if is_musl(&asset.name) != (target.libc == Some(Libc::Gnu)) {
         priority += 1;
     }
 
@@ -290,7 +355,7 @@ fn priority(asset: &Asset) -> u64 {
     priority
 }
 
-mod models {
+pub(super) mod models {
     use serde::Deserialize;
     use url::Url;
 
@@ -372,3 +437,33 @@ mod models {
         pub updated_at:           String,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn api(host: Option<&str>, api: Option<&str>) -> String {
+        let url = |s: &str| s.parse::<Url>().unwrap();
+        Github::new(host.map(url).as_ref(), api.map(url).as_ref())
+            .unwrap()
+            .api
+            .to_string()
+    }
+
+    #[test]
+    fn resolve_api() {
+        assert_eq!(api(None, None), "https://api.github.com/");
+        assert_eq!(api(Some("https://github.com"), None), "https://api.github.com/");
+        assert_eq!(
+            api(Some("https://git.example.com"), None),
+            "https://git.example.com/api/v3"
+        );
+        assert_eq!(
+            api(Some("https://example.com/git/"), None),
+            "https://example.com/git/api/v3"
+        );
+        let custom = "https://git.example.com/custom/api";
+        assert_eq!(api(Some("https://git.example.com"), Some(custom)), custom);
+        assert_eq!(api(None, Some(custom)), custom);
+    }
+}
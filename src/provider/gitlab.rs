@@ -0,0 +1,179 @@
+use anyhow::{anyhow, Context as _, Result};
+use models::{Link, Project, Release};
+use ureq::Agent;
+use url::Url;
+
+use super::{github::models as gh, list_pages, new_client, Forge};
+
+const GITLAB_HOST: &str = "https://gitlab.com";
+
+pub struct Gitlab {
+    client: Agent,
+
+    /// The base URL of the GitLab instance.
+    host: Url,
+}
+
+impl Gitlab {
+    pub fn new(host: Option<Url>) -> Result<Self> {
+        let host = match host {
+            Some(host) => host,
+            None => GITLAB_HOST.parse()?,
+        };
+
+        let agent = new_client("GITLAB_TOKEN", &host);
+
+        Ok(Gitlab { client: agent, host })
+    }
+
+    /// Build the API endpoint of the given project. The project path is url-encoded as GitLab expects.
+    fn endpoint(&self, repo: &str, segments: &[&str]) -> Result<Url> {
+        let mut url = self.host.clone();
+        url.path_segments_mut()
+            .map_err(|_| anyhow!("Invalid GitLab host: `{}`", self.host))?
+            .pop_if_empty()
+            .extend(["api", "v4", "projects", repo])
+            .extend(segments);
+        Ok(url)
+    }
+
+    pub fn get_release(&self, repo: &str, version: Option<&str>) -> Result<Release> {
+        let url = match version {
+            Some(version) => self.endpoint(repo, &["releases", version])?,
+            None => self.endpoint(repo, &["releases", "permalink", "latest"])?,
+        };
+        self.client
+            .get(url.as_str())
+            .call()
+            .context(format!(
+                "failed to get release: `{repo}@{version}`",
+                version = version.unwrap_or("latest")
+            ))?
+            .into_json()
+            .map_err(Into::into)
+    }
+
+    pub fn list_releases(&self, repo: &str) -> Result<Vec<Release>> {
+        let url = self.endpoint(repo, &["releases"])?;
+
+        list_pages(&self.client, &url, "per_page").context(format!("failed to list releases: `{repo}`"))
+    }
+
+    pub fn get_links(&self, repo: &str, tag: &str) -> Result<Vec<Link>> {
+        let url = self.endpoint(repo, &["releases", tag, "assets", "links"])?;
+        self.client
+            .get(url.as_str())
+            .call()
+            .context(format!("failed to get release links: `{repo}@{tag}`"))?
+            .into_json()
+            .map_err(Into::into)
+    }
+
+    pub fn get_project(&self, repo: &str) -> Result<Project> {
+        let url = self.endpoint(repo, &[])?;
+        self.client
+            .get(url.as_str())
+            .call()
+            .context(format!("failed to get project: `{repo}`"))?
+            .into_json()
+            .map_err(Into::into)
+    }
+}
+
+impl Forge for Gitlab {
+    fn client(&self) -> &Agent {
+        &self.client
+    }
+
+    fn get_release(&self, repo: &str, version: Option<&str>) -> Result<gh::Release> {
+        let release = self.get_release(repo, version)?;
+        let links = self.get_links(repo, &release.tag_name)?;
+        Ok(release.into_github(links))
+    }
+
+    fn list_releases(&self, repo: &str) -> Result<Vec<gh::Release>> {
+        let releases = self.list_releases(repo)?;
+        // asset links are fetched only for the chosen release
+        Ok(releases
+            .into_iter()
+            .map(|release| release.into_github(vec![]))
+            .collect())
+    }
+
+    fn get_description(&self, repo: &str) -> Option<String> {
+        self.get_project(repo).ok().and_then(|project| project.description)
+    }
+}
+
+mod models {
+    use serde::Deserialize;
+    use url::Url;
+
+    use crate::provider::github::models as gh;
+
+    #[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
+    pub struct Project {
+        pub id:                  u64,
+        pub name:                String,
+        pub path_with_namespace: String,
+        pub description:         Option<String>,
+        pub default_branch:      Option<String>,
+        pub web_url:             Option<Url>,
+    }
+
+    #[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
+    pub struct Release {
+        pub name:             Option<String>,
+        pub tag_name:         String,
+        pub description:      Option<String>,
+        pub created_at:       Option<String>,
+        pub released_at:      Option<String>,
+        #[serde(default)]
+        pub upcoming_release: bool,
+    }
+
+    #[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
+    pub struct Link {
+        pub id:               u64,
+        pub name:             String,
+        pub url:              Url,
+        pub direct_asset_url: Option<Url>,
+        pub link_type:        Option<String>,
+    }
+
+    impl Release {
+        /// Map the release and its asset links onto the GitHub release model.
+        pub fn into_github(self, links: Vec<Link>) -> gh::Release {
+            gh::Release {
+                name:             self.name,
+                body:             self.description,
+                tag_name:         self.tag_name,
+                target_commitish: Default::default(),
+                tarball_url:      None,
+                zipball_url:      None,
+                draft:            false,
+                prerelease:       self.upcoming_release,
+                created_at:       self.created_at,
+                published_at:     self.released_at,
+                assets:           links.into_iter().map(Link::into_github).collect(),
+            }
+        }
+    }
+
+    impl Link {
+        pub fn into_github(self) -> gh::Asset {
+            gh::Asset {
+                name:                 self.name,
+                browser_download_url: self.direct_asset_url.unwrap_or_else(|| self.url.clone()),
+                url:                  self.url,
+                label:                None,
+                state:                "uploaded".to_owned(),
+                content_type:         Default::default(),
+                size:                 Default::default(),
+                download_count:       Default::default(),
+                created_at:           Default::default(),
+                updated_at:           Default::default(),
+            }
+        }
+    }
+}
@@ -1,15 +1,526 @@
+mod direct;
+mod gitea;
 mod github;
+mod gitlab;
+mod signature;
 
-use anyhow::Result;
+use std::{cmp::Reverse, collections::BTreeMap, env, fs, io::Read as _};
+
+use anyhow::{anyhow, Context as _, Result};
+use semver::Version;
+use serde::de::DeserializeOwned;
+use tracing::trace;
+use ureq::Agent;
+use url::Url;
 
 use crate::{
-    config::{LockedPackage, Package},
+    config::{Channel, LockedAsset, LockedPackage, Package, Source, Verify},
     context::Context,
+    util::{
+        glob_match,
+        http::{BearerAuthMiddleware, UreqExt as _},
+        mkdir_p,
+        pattern::Pattern,
+        remove_file_if_exists,
+        sha256_file,
+        target::{Libc, Target},
+        verify_sha256,
+        version::{parse_constraint, parse_tag},
+    },
+};
+
+use github::{
+    filter_assets,
+    find_checksum,
+    is_metadata,
+    models::{Asset, Release},
 };
 
+pub use direct::Direct;
+pub use gitea::Gitea;
 pub use github::Github;
+pub use gitlab::Gitlab;
 
 pub trait Provider {
+    /// Resolve the version of the package to be installed, without downloading anything.
+    fn resolve_version(&self, pkg: &Package) -> Result<String>;
     fn download(&self, ctx: &Context, pkg: &Package) -> Result<LockedPackage>;
     fn download_locked(&self, ctx: &Context, pkg: &LockedPackage) -> Result<()>;
+    /// List the names of the assets published along with the locked one, empty if unknown.
+    fn list_assets(&self, pkg: &LockedPackage) -> Result<Vec<String>>;
+}
+
+/// Create the provider serving packages from the given source.
+pub fn new(source: &Source) -> Result<Box<dyn Provider>> {
+    Ok(match source {
+        Source::Github { host, api, .. } => Box::new(Github::new(host.as_ref(), api.as_ref())?),
+        Source::Gitlab { host, .. } => Box::new(Gitlab::new(host.clone())?),
+        Source::Gitea { host, .. } => Box::new(Gitea::new(host.clone())?),
+        Source::Url { .. } => Box::new(Direct::new()?),
+    })
+}
+
+/// A code forge publishing package assets through releases.
+///
+/// Releases are mapped onto the GitHub models so that the asset selection logic can be shared.
+trait Forge {
+    fn client(&self) -> &Agent;
+
+    /// Get the release of the given version, or the latest one if not specified.
+    fn get_release(&self, repo: &str, version: Option<&str>) -> Result<Release>;
+
+    /// List the releases of the given repository, assets are not necessarily included.
+    fn list_releases(&self, repo: &str) -> Result<Vec<Release>>;
+
+    /// Get the description of the given repository.
+    fn get_description(&self, repo: &str) -> Option<String>;
+
+    /// Find the release to install for the package.
+    ///
+    /// The version is either an exact tag or a semver constraint matched against the published releases. Without a
+    /// version, the latest release of the package's channel is used.
+    fn find_release(&self, repo: &str, pkg: &Package) -> Result<Release> {
+        let version = pkg.version.as_deref();
+        let req = version.map(parse_constraint).transpose()?.flatten();
+        if version.is_some() && req.is_none() {
+            return self.get_release(repo, version);
+        }
+        if req.is_none() && pkg.channel == Channel::Stable && pkg.tag.is_none() {
+            return self.get_release(repo, None);
+        }
+
+        let pattern = match (&pkg.tag, pkg.channel) {
+            (Some(tag), _) => Some(tag.as_str()),
+            (None, Channel::Nightly) => Some("nightly*"),
+            (None, _) => None,
+        };
+        let candidates = self.list_releases(repo)?.into_iter().filter(|release| {
+            !release.draft
+                && (pkg.channel != Channel::Stable || !release.prerelease)
+                && pattern.map_or(true, |pattern| glob_match(pattern, &release.tag_name))
+        });
+
+        let tag = match req {
+            // the newest release, or the first listed one if the publishing time is unknown
+            None => candidates
+                .min_by_key(|release| Reverse(release.published_at.clone().or(release.created_at.clone())))
+                .map(|release| release.tag_name),
+            Some(req) => candidates
+                .filter_map(|release| parse_tag(&release.tag_name).map(|v| (v, release.tag_name)))
+                .filter(|(v, _)| match pkg.channel {
+                    Channel::Stable => req.matches(v),
+                    // let prereleases match the constraint of their release, e.g. `2.0.0-beta` matches `^2`
+                    _ => req.matches(v) || req.matches(&Version::new(v.major, v.minor, v.patch)),
+                })
+                .max_by(|(a, _), (b, _)| a.cmp(b))
+                .map(|(_, tag)| tag),
+        };
+        let version = version.or(pattern).unwrap_or("latest");
+        let tag = tag.ok_or_else(|| {
+            anyhow!(
+                "No release of {repo} matches `{version}` in the {:?} channel",
+                pkg.channel
+            )
+        })?;
+
+        self.get_release(repo, Some(&tag))
+    }
+}
+
+/// Build the patterns of the package to include and exclude assets of the release for the target platform.
+fn asset_patterns(pkg: &Package, tag: &str, target: &Target) -> Result<(Option<Pattern>, Vec<Pattern>)> {
+    // the version in asset names rarely has the prefix of the tag, e.g. `v1.2.3` or `release-1.2.3`
+    let version = tag.trim_start_matches(|c: char| !c.is_ascii_digit());
+    let triple = target.triple();
+    let vars = [
+        ("version", version),
+        ("os", target.os.as_str()),
+        ("arch", target.arch.as_str()),
+        ("target", triple.as_str()),
+    ];
+
+    let include = pkg
+        .asset
+        .as_deref()
+        .map(|asset| Pattern::new(asset, &vars))
+        .transpose()?;
+    let exclude = pkg
+        .exclude
+        .iter()
+        .map(|exclude| Pattern::new(exclude, &vars))
+        .collect::<Result<_>>()?;
+    Ok((include, exclude))
+}
+
+/// Select the asset of the release to install on the target platform.
+fn select_asset<'a>(ctx: &Context, pkg: &Package, release: &'a Release, target: &Target) -> Result<&'a Asset> {
+    let repo = pkg.source.repo().unwrap_or(&pkg.name);
+    // the host libc is only detected if the assets aren't locked for other machines to share
+    let libc = match pkg.locked_targets(&ctx.target).is_empty() {
+        true => pkg.libc.resolve(target).or_else(Libc::detect),
+        false => pkg.libc.resolve(target),
+    };
+    let target = &Target { libc, ..target.clone() };
+    let (include, exclude) = asset_patterns(pkg, &release.tag_name, target)?;
+    let asset = filter_assets(release, include.as_ref(), &exclude, target).context(format!(
+        "failed to select asset for {repo}@{} on {target}",
+        release.tag_name
+    ))?;
+    let asset = asset.ok_or_else(|| anyhow!("No matching asset found for {repo}@{} on {target}", release.tag_name))?;
+    ctx.log_verbose_status("Filtered", &asset.name);
+    Ok(asset)
+}
+
+/// Download the asset into the cache directory and verify it, returns the asset to lock.
+///
+/// A cached asset is downloaded again if it fails the verification, or if it can't be verified at all, as it may be
+/// stale.
+fn fetch_asset(client: &Agent, ctx: &Context, pkg: &Package, release: &Release, asset: &Asset) -> Result<LockedAsset> {
+    let path = ctx.cache_dir.join(&asset.name);
+
+    // skip download if the asset already exists, except nightly builds which are republished under the same name
+    let cached = path.exists() && pkg.channel != Channel::Nightly;
+    if cached {
+        ctx.log_verbose_status("Skipped", format!("Asset already exists: {}", asset.name));
+    } else {
+        download_asset(client, ctx, &asset.name, asset.browser_download_url.clone())?;
+    }
+
+    let refetch = || {
+        download_asset(client, ctx, &asset.name, asset.browser_download_url.clone())?;
+        verify_asset(client, ctx, pkg, release, asset)
+    };
+    let sha256 = match verify_asset(client, ctx, pkg, release, asset) {
+        Ok(None) if cached => {
+            ctx.log_verbose_status(
+                "Unverified",
+                format!("Cached asset will be downloaded again: {}", asset.name),
+            );
+            refetch()?
+        }
+        Err(e) if cached => {
+            ctx.log_warning("Unverified", format!("Cached asset will be downloaded again: {e:#}"));
+            refetch()?
+        }
+        result => result?,
+    };
+
+    // pin the downloaded content even if it can't be verified
+    let sha256 = match sha256 {
+        Some(sha256) => sha256,
+        None => sha256_file(&path)?,
+    };
+
+    Ok(LockedAsset {
+        filename:     asset.name.clone(),
+        download_url: asset.browser_download_url.clone().into(),
+        sha256:       sha256.into(),
+    })
+}
+
+/// Verify the cached asset with its signature and published checksum, returns the verified digest, or `None` if
+/// there is nothing to verify it with.
+fn verify_asset(
+    client: &Agent,
+    ctx: &Context,
+    pkg: &Package,
+    release: &Release,
+    asset: &Asset,
+) -> Result<Option<String>> {
+    let mut verified = false;
+    if let Some(verify) = &pkg.verify {
+        let name = format!("{}{}", asset.name, verify.suffix());
+        let signature = release
+            .assets
+            .iter()
+            .find(|signature| signature.name == name)
+            .ok_or_else(|| anyhow!("No signature found for {}: {name}", asset.name))?;
+        verify_signature(client, ctx, verify, &asset.name, signature.browser_download_url.clone())?;
+        verified = true;
+    }
+
+    match verify_checksum(client, ctx, release, asset)? {
+        Some(sha256) => Ok(Some(sha256)),
+        None if verified => Ok(Some(sha256_file(ctx.cache_dir.join(&asset.name))?)),
+        None => Ok(None),
+    }
+}
+
+/// Resolve the assets of the package for each of the platforms to lock, reusing the given one for the target platform.
+fn lock_targets(
+    ctx: &Context,
+    pkg: &Package,
+    asset: &LockedAsset,
+    mut resolve: impl FnMut(&Target) -> Result<LockedAsset>,
+) -> Result<BTreeMap<Target, LockedAsset>> {
+    pkg.locked_targets(&ctx.target)
+        .into_iter()
+        .map(|target| {
+            let asset = match target == ctx.target {
+                true => asset.clone(),
+                false => resolve(&target)?,
+            };
+            Ok((target, asset))
+        })
+        .collect()
+}
+
+/// Verify the cached asset against the checksum published in the release, returns the verified digest.
+///
+/// The cached asset is removed on mismatch so that it will be downloaded again next time.
+fn verify_checksum(client: &Agent, ctx: &Context, release: &Release, asset: &Asset) -> Result<Option<String>> {
+    let Some(checksum) = find_checksum(release, asset) else {
+        ctx.log_verbose_warning("Unverified", format!("No checksum found for {}", asset.name));
+        return Ok(None);
+    };
+
+    let content = client
+        .get(checksum.browser_download_url.as_str())
+        .call()
+        .context(format!("failed to download checksum: {}", checksum.name))?
+        .into_string()?;
+
+    let Some(expected) = parse_checksum(&content, &asset.name) else {
+        ctx.log_warning(
+            "Unverified",
+            format!("No checksum of {} found in {}", asset.name, checksum.name),
+        );
+        return Ok(None);
+    };
+
+    let path = ctx.cache_dir.join(&asset.name);
+    if let Err(e) = verify_sha256(&path, &expected) {
+        remove_file_if_exists(&path)?;
+        return Err(e);
+    }
+    ctx.log_verbose_status("Verified", format!("{} with {}", asset.name, checksum.name));
+
+    Ok(Some(expected.to_lowercase()))
+}
+
+/// Find the SHA-256 digest of a file in a checksum file, which contains either a bare digest or lines in the format
+/// of `sha256sum` or BSD style `SHA256 (file) = digest`.
+fn parse_checksum(content: &str, name: &str) -> Option<String> {
+    let is_digest = |s: &str| s.len() == 64 && s.chars().all(|c| c.is_ascii_hexdigit());
+    let is_name = |s: &str| s.trim_start_matches('*').rsplit('/').next() == Some(name);
+
+    content.lines().find_map(|line| {
+        trace!("parsing checksum line: {line}");
+        let parts: Vec<_> = line.split_whitespace().collect();
+        match &parts[..] {
+            [digest] if is_digest(digest) => Some(digest.to_string()),
+            [digest, file] if is_digest(digest) && is_name(file) => Some(digest.to_string()),
+            ["SHA256", file, "=", digest] if is_digest(digest) => file
+                .strip_prefix('(')
+                .and_then(|file| file.strip_suffix(')'))
+                .filter(|file| is_name(file))
+                .map(|_| digest.to_string()),
+            _ => None,
+        }
+    })
+}
+
+/// Verify the cached asset with the signature downloaded from the given URL.
+///
+/// The cached asset is removed on mismatch so that it will be downloaded again next time.
+fn verify_signature(client: &Agent, ctx: &Context, verify: &Verify, name: &str, url: Url) -> Result<()> {
+    let mut signature = Vec::new();
+    client
+        .get(url.as_str())
+        .call()
+        .context(format!("failed to download signature: {url}"))?
+        .into_reader()
+        .read_to_end(&mut signature)?;
+
+    let path = ctx.cache_dir.join(name);
+    let data = fs::read(&path)?;
+    if let Err(e) = verify.verify(&data, &signature) {
+        remove_file_if_exists(&path)?;
+        return Err(e.context(format!("failed to verify signature of {name}")));
+    }
+    ctx.log_verbose_status("Verified", format!("{name} with {url}"));
+
+    Ok(())
+}
+
+/// The page size and the maximum number of pages when listing releases.
+const RELEASES_PER_PAGE: usize = 50;
+const RELEASES_MAX_PAGES: usize = 20;
+
+/// Create the client of a forge, authenticated with the token from the given environment variable or its `RPK_`
+/// prefixed variant.
+fn new_client(token_var: &str, host: &Url) -> Agent {
+    let token = env::var(token_var)
+        .or_else(|_| env::var(format!("RPK_{token_var}")))
+        .ok();
+    ureq::AgentBuilder::new()
+        .user_agent("rpk")
+        .middleware(BearerAuthMiddleware::new(token, host))
+        .build()
+}
+
+/// List the items of a paginated endpoint page by page, the page size is set by the given query parameter.
+fn list_pages<T: DeserializeOwned>(client: &Agent, url: &Url, size_query: &str) -> Result<Vec<T>> {
+    let mut items = Vec::new();
+    for page in 1..=RELEASES_MAX_PAGES {
+        let batch: Vec<T> = client
+            .get(url.as_str())
+            .query(size_query, &RELEASES_PER_PAGE.to_string())
+            .query("page", &page.to_string())
+            .call()?
+            .into_json()?;
+        let done = batch.len() < RELEASES_PER_PAGE;
+        items.extend(batch);
+        if done {
+            break;
+        }
+    }
+    Ok(items)
+}
+
+/// Download the asset from the given URL into the cache directory.
+fn download_asset(client: &Agent, ctx: &Context, name: &str, url: Url) -> Result<()> {
+    ctx.log_verbose_status("Downloading", &url);
+    let path = ctx.cache_dir.join(name);
+    if let Some(dir) = path.parent() {
+        mkdir_p(dir)?;
+    }
+    client.download(url, path).context("failed to download asset")?;
+    ctx.log_status("Downloaded", name);
+    Ok(())
+}
+
+impl<T: Forge> Provider for T {
+    fn resolve_version(&self, pkg: &Package) -> Result<String> {
+        let repo = pkg.source.repo().context("source has no repository")?;
+        let release = self.find_release(repo, pkg)?;
+        Ok(release.tag_name)
+    }
+
+    fn download(&self, ctx: &Context, pkg: &Package) -> Result<LockedPackage> {
+        let repo = pkg.source.repo().context("source has no repository")?;
+
+        let release = self.find_release(repo, pkg)?;
+        ctx.log_verbose_status("Fetched", format!("{repo}@{version}", version = release.tag_name));
+
+        let asset = select_asset(ctx, pkg, &release, &ctx.target)?;
+        let asset = fetch_asset(self.client(), ctx, pkg, &release, asset)?;
+        let targets = lock_targets(ctx, pkg, &asset, |target| {
+            let asset = select_asset(ctx, pkg, &release, target)?;
+            fetch_asset(self.client(), ctx, pkg, &release, asset)
+        })?;
+
+        // get description from the repository if not provided
+        let desc = match &pkg.desc {
+            Some(desc) => desc.clone().into(),
+            None => self.get_description(repo),
+        };
+
+        Ok(LockedPackage {
+            name: pkg.name.clone(),
+            version: release.tag_name.clone(),
+            source: pkg.source.clone(),
+            desc: desc.map(|desc| desc.trim().to_string()),
+            filename: asset.filename,
+            download_url: asset.download_url,
+            sha256: asset.sha256,
+            bins: Default::default(),
+            completions: None,
+            manpages: None,
+            extract_appimage: pkg.extract_appimage,
+            targets,
+        })
+    }
+
+    fn download_locked(&self, ctx: &Context, lpkg: &LockedPackage) -> Result<()> {
+        let path = ctx.cache_dir.join(&lpkg.filename);
+
+        // skip download if the asset already exists
+        if path.exists() {
+            ctx.log_verbose_status("Skipped", format!("Asset already exists: {}", lpkg.filename));
+            return Ok(());
+        }
+
+        let repo = lpkg.source.repo().context("source has no repository")?;
+        let version = &lpkg.version;
+
+        let download_url = match lpkg.download_url.as_ref() {
+            Some(url) => url.clone(),
+            None => {
+                let release = self.get_release(repo, Some(version))?;
+                ctx.log_verbose_status("Fetched", format!("{repo}@{version}"));
+                let asset = release
+                    .assets
+                    .iter()
+                    .find(|asset| asset.name == lpkg.filename)
+                    .ok_or_else(|| anyhow!("Asset not found"))?;
+                asset.browser_download_url.clone()
+            }
+        };
+
+        download_asset(self.client(), ctx, &lpkg.filename, download_url)?;
+
+        Ok(())
+    }
+
+    fn list_assets(&self, lpkg: &LockedPackage) -> Result<Vec<String>> {
+        let repo = lpkg.source.repo().context("source has no repository")?;
+        let release = self.get_release(repo, Some(&lpkg.version))?;
+        Ok(release
+            .assets
+            .into_iter()
+            .map(|asset| asset.name)
+            .filter(|name| !is_metadata(name))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIGEST: &str = "09c9b687b1a96c622cb0ae2ea6c1afdf907fadd81d4f26a5da37497cacf99da2";
+    const OTHER: &str = "edff58f2a441868dc58c35d06f2b1c86e12e12bedfaa793a49c227672f77566e";
+
+    #[test]
+    fn parse_bare_checksum() {
+        assert_eq!(
+            parse_checksum(&format!("{DIGEST}\n"), "tool.tar.gz").as_deref(),
+            Some(DIGEST)
+        );
+        assert_eq!(parse_checksum("not a digest\n", "tool.tar.gz"), None);
+    }
+
+    #[test]
+    fn parse_sha256sum_checksums() {
+        let content = format!("{OTHER}  other.tar.gz\n{DIGEST}  tool.tar.gz\n");
+        assert_eq!(parse_checksum(&content, "tool.tar.gz").as_deref(), Some(DIGEST));
+        assert_eq!(parse_checksum(&content, "other.tar.gz").as_deref(), Some(OTHER));
+        assert_eq!(parse_checksum(&content, "missing.tar.gz"), None);
+
+        // binary mode and directories in the file names
+        let content = format!("{DIGEST} *dist/tool.tar.gz\n");
+        assert_eq!(parse_checksum(&content, "tool.tar.gz").as_deref(), Some(DIGEST));
+        assert_eq!(parse_checksum(&content, "dist"), None);
+    }
+
+    #[test]
+    fn parse_bsd_checksums() {
+        let content = format!("SHA256 (other.tar.gz) = {OTHER}\nSHA256 (tool.tar.gz) = {DIGEST}\n");
+        assert_eq!(parse_checksum(&content, "tool.tar.gz").as_deref(), Some(DIGEST));
+        assert_eq!(parse_checksum(&content, "tool"), None);
+
+        let content = format!("SHA512 (tool.tar.gz) = {DIGEST}\n");
+        assert_eq!(parse_checksum(&content, "tool.tar.gz"), None);
+    }
+
+    #[test]
+    fn parse_checksum_ignores_invalid_digests() {
+        let content = format!(
+            "{}  tool.tar.gz\n{}  tool.tar.gz\n",
+            &DIGEST[1..],
+            DIGEST.replace('0', "g")
+        );
+        assert_eq!(parse_checksum(&content, "tool.tar.gz"), None);
+    }
 }
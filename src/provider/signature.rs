@@ -0,0 +1,182 @@
+//! Offline signature verification of downloaded assets.
+
+use std::str;
+
+use anyhow::{anyhow, Context as _, Result};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use p256::{ecdsa::signature::Verifier as _, pkcs8::DecodePublicKey as _};
+
+use crate::config::Verify;
+
+impl Verify {
+    /// The suffix of the asset holding the signature.
+    pub fn suffix(&self) -> &'static str {
+        match self {
+            Verify::Minisign(_) => ".minisig",
+            Verify::Ed25519(_) | Verify::Cosign(_) => ".sig",
+        }
+    }
+
+    /// Verify the signature of the data with the configured public key.
+    pub fn verify(&self, data: &[u8], signature: &[u8]) -> Result<()> {
+        match self {
+            Verify::Minisign(key) => {
+                let key = match key.trim() {
+                    // the content of a `.pub` file with the untrusted comment
+                    key if key.contains('\n') => minisign_verify::PublicKey::decode(key),
+                    key => minisign_verify::PublicKey::from_base64(key),
+                }
+                .context("invalid minisign public key")?;
+                let signature = minisign_verify::Signature::decode(str::from_utf8(signature)?)
+                    .context("invalid minisign signature")?;
+                key.verify(data, &signature, false)
+                    .context("minisign signature mismatch")
+            }
+            Verify::Ed25519(key) => {
+                let key: [u8; 32] = decode_base64(key)?
+                    .try_into()
+                    .map_err(|_| anyhow!("invalid ed25519 public key length"))?;
+                let key = ed25519_dalek::VerifyingKey::from_bytes(&key).context("invalid ed25519 public key")?;
+                // the signature is either raw bytes or base64 encoded
+                let signature = match <[u8; 64]>::try_from(signature) {
+                    Ok(signature) => signature,
+                    Err(_) => decode_base64(str::from_utf8(signature)?)?
+                        .try_into()
+                        .map_err(|_| anyhow!("invalid ed25519 signature length"))?,
+                };
+                let signature = ed25519_dalek::Signature::from_bytes(&signature);
+                key.verify_strict(data, &signature)
+                    .context("ed25519 signature mismatch")
+            }
+            Verify::Cosign(key) => {
+                let key = p256::ecdsa::VerifyingKey::from_public_key_der(&decode_pem(key)?)
+                    .map_err(|e| anyhow!("invalid cosign public key: {e}"))?;
+                let signature = p256::ecdsa::Signature::from_der(&decode_base64(str::from_utf8(signature)?)?)
+                    .context("invalid cosign signature")?;
+                key.verify(data, &signature).context("cosign signature mismatch")
+            }
+        }
+    }
+}
+
+fn decode_base64(s: &str) -> Result<Vec<u8>> {
+    let s: String = s.split_whitespace().collect();
+    STANDARD.decode(s).context("invalid base64 encoding")
+}
+
+/// Decode the DER content of a PEM block.
+fn decode_pem(s: &str) -> Result<Vec<u8>> {
+    let body: String = s
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with("-----"))
+        .collect();
+    decode_base64(&body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DATA: &[u8] = b"rpk signature test data\n";
+
+    const MINISIGN_KEY: &str = "RWQBAgMEBQYHCO1JKMYo0cLG6ukDOJBZlWEpWSc6XGP5NjbBRhSshzfR";
+    const MINISIGN_OTHER_KEY: &str = "RWQBAgMEBQYHCMqTrBcFGHBx1nuDx/8O/oEI6OxFMFdddyaHkzPb2r58";
+    const MINISIGN_SIG: &str = "untrusted comment: signature from rpk test key
+RUQBAgMEBQYHCCY/DgSG6ZcPrAHRNeJDWzlSC06mzAU4za2C+pkE8O+u6/m2J03EJ1xFR8qdA60zG5MUrRXk+LAkWjq2IJn2uAc=
+trusted comment: timestamp:1700000000\tfile:tool.tar.gz
+TPpV8iWRsB3XtKl6V6dqlQasUmT74d8eTqKk0m+QBrlVTM8QNO/5id+iwy10KuzAihE3d2knmDBF0XSXwvhuAw==
+";
+
+    const ED25519_KEY: &str = "iojj3XQJ8ZX9UtstPLpdcspnCb8dlBIb83SIAbQPb1w=";
+    const ED25519_OTHER_KEY: &str = "gTl3Dqh9F19Wo1Rmw0x+zMuNipG07jeiXfYPW4/Js5Q=";
+    const ED25519_SIG: &str =
+        "r+GwrLrF2EMHRpyNgFR1D4wGtmJUplffq5Fo0yrz8K2UGl6lufXDejGZp24zM26sAKfhqUs0MFdyIho1OZXVBg==";
+
+    const COSIGN_KEY: &str = "-----BEGIN PUBLIC KEY-----
+MFkwEwYHKoZIzj0CAQYIKoZIzj0DAQcDQgAEJu/OvQ7p40pmkYfhizqRIrL3M5Rb
+ZJzJ+fkh6fna2BKQI4venMe7Mw0VDGdwTdJa5wVSBXRLbzG/QHB0WHLQ5g==
+-----END PUBLIC KEY-----
+";
+    const COSIGN_OTHER_KEY: &str = "-----BEGIN PUBLIC KEY-----
+MFkwEwYHKoZIzj0CAQYIKoZIzj0DAQcDQgAEMZ1zwHtoJcm40nqoZbTFG0QEg6Ry
+qKHj3ZMIs/xYw1WlOiX3sTlkBemRjxcTYnev9HXxJmpqzJL/8hhaisNFBw==
+-----END PUBLIC KEY-----
+";
+    const COSIGN_SIG: &str =
+        "MEUCIGzU5HnOqKYAPTewpNEhho97bOEfh7kCmSBcLotOlwG3AiEA/XZMWSH9E9psROysq7ohvhl/V/BnRg7U/OWEsa2MmDo=";
+
+    fn err(verify: Verify, data: &[u8], signature: &[u8]) -> String {
+        verify.verify(data, signature).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn minisign() {
+        let verify = |key: &str| Verify::Minisign(key.into());
+        let sig = MINISIGN_SIG.as_bytes();
+        verify(MINISIGN_KEY).verify(DATA, sig).unwrap();
+        // the content of a `.pub` file
+        let pub_file = format!("untrusted comment: minisign public key\n{MINISIGN_KEY}\n");
+        verify(&pub_file).verify(DATA, sig).unwrap();
+
+        assert_eq!(
+            err(verify(MINISIGN_KEY), b"tampered", sig),
+            "minisign signature mismatch"
+        );
+        assert_eq!(
+            err(verify(MINISIGN_OTHER_KEY), DATA, sig),
+            "minisign signature mismatch"
+        );
+        let tampered_comment = MINISIGN_SIG.replace("1700000000", "1700000001");
+        assert_eq!(
+            err(verify(MINISIGN_KEY), DATA, tampered_comment.as_bytes()),
+            "minisign signature mismatch"
+        );
+        assert_eq!(
+            err(verify(MINISIGN_KEY), DATA, b"not a signature"),
+            "invalid minisign signature"
+        );
+        assert_eq!(err(verify("not a key"), DATA, sig), "invalid minisign public key");
+    }
+
+    #[test]
+    fn ed25519() {
+        let verify = |key: &str| Verify::Ed25519(key.into());
+        let raw = decode_base64(ED25519_SIG).unwrap();
+        let base64 = ED25519_SIG.as_bytes();
+        verify(ED25519_KEY).verify(DATA, &raw).unwrap();
+        verify(ED25519_KEY).verify(DATA, base64).unwrap();
+
+        for sig in [&raw[..], base64] {
+            assert_eq!(err(verify(ED25519_KEY), b"tampered", sig), "ed25519 signature mismatch");
+            assert_eq!(err(verify(ED25519_OTHER_KEY), DATA, sig), "ed25519 signature mismatch");
+        }
+        let mut flipped = raw.clone();
+        flipped[0] ^= 1;
+        assert_eq!(err(verify(ED25519_KEY), DATA, &flipped), "ed25519 signature mismatch");
+        // a truncated raw signature is neither 64 bytes nor base64 text
+        assert!(verify(ED25519_KEY).verify(DATA, &raw[..63]).is_err());
+        assert_eq!(
+            err(verify(ED25519_KEY), DATA, b"not base64!"),
+            "invalid base64 encoding"
+        );
+        assert_eq!(
+            err(verify(ED25519_KEY), DATA, b"AAAA"),
+            "invalid ed25519 signature length"
+        );
+        assert_eq!(err(verify("AAAA"), DATA, &raw), "invalid ed25519 public key length");
+    }
+
+    #[test]
+    fn cosign() {
+        let verify = |key: &str| Verify::Cosign(key.into());
+        let sig = COSIGN_SIG.as_bytes();
+        verify(COSIGN_KEY).verify(DATA, sig).unwrap();
+
+        assert_eq!(err(verify(COSIGN_KEY), b"tampered", sig), "cosign signature mismatch");
+        assert_eq!(err(verify(COSIGN_OTHER_KEY), DATA, sig), "cosign signature mismatch");
+        assert_eq!(err(verify(COSIGN_KEY), DATA, b"AAAA"), "invalid cosign signature");
+        assert_eq!(err(verify(COSIGN_KEY), DATA, b"not base64!"), "invalid base64 encoding");
+        assert!(err(verify(ED25519_KEY), DATA, sig).starts_with("invalid cosign public key"));
+    }
+}
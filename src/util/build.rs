@@ -20,6 +20,9 @@ pub const CRATE_NAME: &str = env!("CARGO_PKG_NAME");
 /// This is the version defined in the Cargo manifest.
 pub const CRATE_RELEASE: &str = env!("CARGO_PKG_VERSION");
 
+/// The target triple this crate was compiled for.
+pub const CRATE_TARGET: &str = env!("TARGET");
+
 /// The version including any available Git information.
 #[allow(clippy::const_is_empty)]
 pub const CRATE_VERSION: &str = {
@@ -55,7 +58,7 @@ pub const CRATE_VERBOSE_VERSION: &str = constcat::concat!(
     "\n  commit-date: ",
     env_or_default!("GIT_COMMIT_DATE", "unknown"),
     "\n  target: ",
-    env!("TARGET"),
+    CRATE_TARGET,
     "\n\nCompiled with:",
     "\n  binary: ",
     env!("RUSTC_VERSION_BINARY"),
@@ -0,0 +1,223 @@
+//! Reading the headers of ELF binaries.
+
+use std::{
+    fs::File,
+    io::{self, Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+};
+
+const MAGIC: &[u8] = b"\x7fELF";
+const PT_INTERP: u32 = 3;
+
+/// The header of an ELF binary, with the fields telling whether it can run on a platform.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Elf {
+    /// The bitness of the binary, either 32 or 64.
+    pub bits:        u8,
+    /// The machine type, like `62` for x86_64.
+    pub machine:     u16,
+    /// The dynamic loader requested by the binary, `None` if it's statically linked.
+    pub interpreter: Option<PathBuf>,
+}
+
+impl Elf {
+    /// Read the header of the binary, `None` if it's not an ELF binary.
+    pub fn read(path: &Path) -> io::Result<Option<Elf>> {
+        let mut file = File::open(path)?;
+
+        let mut header = Vec::new();
+        file.by_ref().take(64).read_to_end(&mut header)?;
+        if header.len() < 52 || !header.starts_with(MAGIC) {
+            return Ok(None);
+        }
+        let is_64 = header[4] == 2;
+        let is_le = header[5] == 1;
+        let field = |bytes: &[u8]| -> u64 {
+            let fold = |acc: u64, byte: &u8| (acc << 8) | u64::from(*byte);
+            match is_le {
+                true => bytes.iter().rev().fold(0, fold),
+                false => bytes.iter().fold(0, fold),
+            }
+        };
+
+        // the offset, entry size and number of the program headers
+        let (phoff, phentsize, phnum) = match is_64 {
+            true if header.len() == 64 => (field(&header[32..40]), field(&header[54..56]), field(&header[56..58])),
+            true => return Ok(None),
+            false => (field(&header[28..32]), field(&header[42..44]), field(&header[44..46])),
+        };
+        if phentsize < if is_64 { 56 } else { 32 } {
+            return Ok(None);
+        }
+
+        let mut elf = Elf {
+            bits:        if is_64 { 64 } else { 32 },
+            machine:     field(&header[18..20]) as u16,
+            interpreter: None,
+        };
+        let mut entry = vec![0; phentsize as usize];
+        for i in 0..phnum {
+            file.seek(SeekFrom::Start(phoff.saturating_add(i * phentsize)))?;
+            file.read_exact(&mut entry)?;
+            if field(&entry[0..4]) != u64::from(PT_INTERP) {
+                continue;
+            }
+
+            let (offset, size) = match is_64 {
+                true => (field(&entry[8..16]), field(&entry[32..40])),
+                false => (field(&entry[4..8]), field(&entry[16..20])),
+            };
+            let mut interpreter = Vec::new();
+            file.seek(SeekFrom::Start(offset))?;
+            file.by_ref().take(size.min(4096)).read_to_end(&mut interpreter)?;
+            let interpreter = interpreter.split(|&b| b == 0).next().unwrap_or_default();
+            elf.interpreter = Some(PathBuf::from(String::from_utf8_lossy(interpreter).into_owned()));
+            break;
+        }
+
+        Ok(Some(elf))
+    }
+
+    /// The architecture of the binary, named like [`std::env::consts::ARCH`].
+    pub fn arch(&self) -> Option<&'static str> {
+        match (self.machine, self.bits) {
+            (3, _) => Some("x86"),
+            (8, _) => Some("mips"),
+            (20, _) => Some("powerpc"),
+            (21, _) => Some("powerpc64"),
+            (22, _) => Some("s390x"),
+            (40, _) => Some("arm"),
+            // binaries of the x32 ABI have the same machine type but are 32-bit, they are not taken as x86_64 ones
+            (62, 64) => Some("x86_64"),
+            (183, 64) => Some("aarch64"),
+            (243, 64) => Some("riscv64"),
+            (258, 64) => Some("loongarch64"),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::util::temp::scratch;
+
+    /// Build an ELF binary with a load segment, followed by the interpreter segment if any.
+    fn build(bits: u8, big_endian: bool, machine: u16, interpreter: Option<&str>) -> Vec<u8> {
+        let put = |buf: &mut Vec<u8>, offset: usize, size: usize, value: u64| {
+            let bytes = value.to_le_bytes();
+            let field = &mut buf[offset..offset + size];
+            field.copy_from_slice(&bytes[..size]);
+            if big_endian {
+                field.reverse();
+            }
+        };
+        let is_64 = bits == 64;
+        let (header_size, entry_size) = if is_64 { (64, 56) } else { (52, 32) };
+        let interp_offset = header_size + 2 * entry_size;
+
+        let mut buf = vec![0; interp_offset];
+        buf[..4].copy_from_slice(MAGIC);
+        buf[4] = if is_64 { 2 } else { 1 };
+        buf[5] = if big_endian { 2 } else { 1 };
+        buf[6] = 1;
+        put(&mut buf, 18, 2, machine.into());
+        let phnum = if interpreter.is_some() { 2 } else { 1 };
+        match is_64 {
+            true => {
+                put(&mut buf, 32, 8, header_size as u64);
+                put(&mut buf, 54, 2, entry_size as u64);
+                put(&mut buf, 56, 2, phnum);
+            }
+            false => {
+                put(&mut buf, 28, 4, header_size as u64);
+                put(&mut buf, 42, 2, entry_size as u64);
+                put(&mut buf, 44, 2, phnum);
+            }
+        }
+
+        // a PT_LOAD segment comes first
+        put(&mut buf, header_size, 4, 1);
+        if let Some(interpreter) = interpreter {
+            let entry = header_size + entry_size;
+            let size = interpreter.len() as u64 + 1;
+            put(&mut buf, entry, 4, PT_INTERP.into());
+            match is_64 {
+                true => {
+                    put(&mut buf, entry + 8, 8, interp_offset as u64);
+                    put(&mut buf, entry + 32, 8, size);
+                }
+                false => {
+                    put(&mut buf, entry + 4, 4, interp_offset as u64);
+                    put(&mut buf, entry + 16, 4, size);
+                }
+            }
+            buf.extend(interpreter.as_bytes());
+            buf.push(0);
+        }
+        buf
+    }
+
+    fn read(name: &str, content: &[u8]) -> Option<Elf> {
+        let tmp = scratch(name);
+        let path = tmp.path().join("bin");
+        fs::write(&path, content).unwrap();
+        Elf::read(&path).unwrap()
+    }
+
+    #[test]
+    fn read_dynamic_binaries() {
+        let loader = "/lib64/ld-linux-x86-64.so.2";
+        let elf = read("x86_64", &build(64, false, 62, Some(loader))).unwrap();
+        assert_eq!(elf, Elf {
+            bits:        64,
+            machine:     62,
+            interpreter: Some(loader.into()),
+        });
+        assert_eq!(elf.arch(), Some("x86_64"));
+
+        let loader = "/lib/ld-musl-armhf.so.1";
+        let elf = read("arm", &build(32, false, 40, Some(loader))).unwrap();
+        assert_eq!(elf, Elf {
+            bits:        32,
+            machine:     40,
+            interpreter: Some(loader.into()),
+        });
+        assert_eq!(elf.arch(), Some("arm"));
+
+        let loader = "/lib/ld64.so.1";
+        let elf = read("s390x", &build(64, true, 22, Some(loader))).unwrap();
+        assert_eq!(elf, Elf {
+            bits:        64,
+            machine:     22,
+            interpreter: Some(loader.into()),
+        });
+        assert_eq!(elf.arch(), Some("s390x"));
+    }
+
+    #[test]
+    fn read_static_binaries() {
+        let elf = read("aarch64", &build(64, false, 183, None)).unwrap();
+        assert_eq!(elf, Elf { bits: 64, machine: 183, interpreter: None });
+        assert_eq!(elf.arch(), Some("aarch64"));
+    }
+
+    #[test]
+    fn unknown_architectures() {
+        // the x32 ABI
+        assert_eq!(read("x32", &build(32, false, 62, None)).unwrap().arch(), None);
+        assert_eq!(read("unknown", &build(64, false, 0xffff, None)).unwrap().arch(), None);
+    }
+
+    #[test]
+    fn read_other_files() {
+        assert_eq!(read("script", b"#!/bin/sh\necho hello\n"), None);
+        assert_eq!(read("empty", b""), None);
+
+        // truncated headers
+        assert_eq!(read("truncated", &build(64, false, 62, None)[..40]), None);
+        assert_eq!(read("truncated-64", &build(64, false, 62, None)[..60]), None);
+    }
+}
@@ -1,25 +1,28 @@
 use std::{
+    ffi::OsString,
     fs,
     io,
     os::unix::{self},
-    path::Path,
+    path::{Path, PathBuf},
 };
 
-use anyhow::Result;
+use anyhow::{bail, Context as _, Result};
 use serde::Deserialize;
+use sha2::{Digest as _, Sha256};
 use tracing::trace;
 
-pub fn symlink_force(src: impl AsRef<Path>, dst: impl AsRef<Path>) -> anyhow::Result<()> {
-    let src = src.as_ref();
+/// Create the symlink under a temporary name next to `dst` like `~name`, which is to be renamed to `dst` so that the
+/// existing one is replaced atomically.
+pub fn symlink_temp(src: impl AsRef<Path>, dst: impl AsRef<Path>) -> anyhow::Result<PathBuf> {
     let dst = dst.as_ref();
+    let name = dst.file_name().context("no filename")?;
+    let mut temp_name = OsString::from("~");
+    temp_name.push(name);
+    let temp_path = dst.with_file_name(temp_name);
 
-    if let Err(e) = fs::remove_file(dst) {
-        if e.kind() != io::ErrorKind::NotFound {
-            return Err(e.into());
-        }
-    }
-
-    unix::fs::symlink(src, dst).map_err(Into::into)
+    remove_file_if_exists(&temp_path)?;
+    unix::fs::symlink(src, &temp_path)?;
+    Ok(temp_path)
 }
 
 pub fn mkdir_p(dir: impl AsRef<Path>) -> anyhow::Result<()> {
@@ -60,3 +63,26 @@ pub fn rm_rf(path: &Path) -> io::Result<()> {
         _ => Err(e),
     })
 }
+
+/// Calculate the SHA-256 digest of a file as a lowercase hex string.
+pub fn sha256_file(path: impl AsRef<Path>) -> Result<String> {
+    let mut file = fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    io::copy(&mut file, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Verify the SHA-256 digest of a file.
+pub fn verify_sha256(path: impl AsRef<Path>, expected: &str) -> Result<()> {
+    let path = path.as_ref();
+    let actual = sha256_file(path)?;
+    if !actual.eq_ignore_ascii_case(expected) {
+        bail!(
+            "checksum mismatch for {}: expected {}, got {}",
+            path.display(),
+            expected,
+            actual
+        );
+    }
+    Ok(())
+}
@@ -4,17 +4,29 @@ use std::{
 };
 
 use ureq::{Middleware, MiddlewareNext, Request, Response};
-use url::Url;
+use url::{Origin, Url};
 
 use super::temp::TempFile;
 
-pub struct BearerAuthMiddleware(pub Option<String>);
+/// Authenticate the requests to the given host, leaving the requests to other hosts, like asset downloads from a CDN,
+/// without the token.
+pub struct BearerAuthMiddleware {
+    token:  Option<String>,
+    origin: Origin,
+}
+
+impl BearerAuthMiddleware {
+    pub fn new(token: Option<String>, host: &Url) -> Self {
+        BearerAuthMiddleware { token, origin: host.origin() }
+    }
+}
 
 impl Middleware for BearerAuthMiddleware {
     fn handle(&self, request: Request, next: MiddlewareNext) -> Result<Response, ureq::Error> {
-        let req = match &self.0 {
-            Some(token) => request.set("Authorization", format!("Bearer {}", token).as_str()),
-            None => request,
+        let same_origin = Url::parse(request.url()).is_ok_and(|url| url.origin() == self.origin);
+        let req = match &self.token {
+            Some(token) if same_origin => request.set("Authorization", format!("Bearer {}", token).as_str()),
+            _ => request,
         };
 
         next.handle(req)
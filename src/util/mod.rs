@@ -1,8 +1,12 @@
 mod build;
 mod fs;
 
+pub mod elf;
 pub mod http;
+pub mod pattern;
+pub mod target;
 pub mod temp;
+pub mod version;
 
 pub use build::*;
 pub use fs::*;
@@ -87,3 +91,63 @@ where
         s.ok_or_else(|| anyhow::anyhow!("failed to convert path to string: {:?}", self.as_ref()))
     }
 }
+
+/// Match the text against a glob pattern, where `*` matches any sequence of characters and `?` matches any single
+/// character.
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let (pattern, text): (Vec<_>, Vec<_>) = (pattern.chars().collect(), text.chars().collect());
+    let (mut p, mut t) = (0, 0);
+    // the position of the last `*` in the pattern and the text position it matched up to
+    let mut backtrack = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                Some((bp, bt)) => {
+                    backtrack = Some((bp, bt + 1));
+                    p = bp + 1;
+                    t = bt + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn glob_match_literals() {
+        assert!(glob_match("nightly", "nightly"));
+        assert!(!glob_match("nightly", "nightly-2024"));
+        assert!(!glob_match("nightly", "night"));
+        assert!(glob_match("", ""));
+        assert!(!glob_match("", "a"));
+    }
+
+    #[test]
+    fn glob_match_wildcards() {
+        assert!(glob_match("nightly*", "nightly"));
+        assert!(glob_match("nightly*", "nightly-2024-08-20"));
+        assert!(glob_match("*", ""));
+        assert!(glob_match("*-linux-*", "tool-linux-x86_64"));
+        assert!(!glob_match("*-linux-*", "tool-linux"));
+        assert!(glob_match("v?.?", "v1.2"));
+        assert!(!glob_match("v?.?", "v1.23"));
+        assert!(glob_match("*.tar.*", "tool.tar.tar.gz"));
+        // backtracking past a partial match
+        assert!(glob_match("*ab", "aab"));
+        assert!(glob_match("a*b*c", "abxbxc"));
+        assert!(!glob_match("a*b*c", "abxbx"));
+    }
+}
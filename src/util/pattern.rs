@@ -0,0 +1,90 @@
+//! Patterns matching the names of release assets.
+
+use std::fmt;
+
+use anyhow::{Context as _, Result};
+use regex::Regex;
+
+use super::glob_match;
+
+/// A glob pattern, or a regex pattern if enclosed in slashes like `/tool_.*_linux/`.
+///
+/// Glob patterns are matched case-insensitively against the whole name, while regex patterns are matched as is.
+#[derive(Debug, Clone)]
+pub enum Pattern {
+    Glob(String),
+    Regex(Regex),
+}
+
+impl Pattern {
+    /// Parse the pattern, expanding the placeholders like `{version}` with the given values.
+    pub fn new(pattern: &str, vars: &[(&str, &str)]) -> Result<Self> {
+        match pattern.strip_prefix('/').and_then(|p| p.strip_suffix('/')) {
+            Some(regex) => {
+                let regex = vars.iter().fold(regex.to_owned(), |regex, (key, value)| {
+                    regex.replace(&format!("{{{key}}}"), &regex::escape(value))
+                });
+                let regex = Regex::new(&regex).context(format!("Invalid regex pattern: `{pattern}`"))?;
+                Ok(Pattern::Regex(regex))
+            }
+            None => {
+                let glob = vars.iter().fold(pattern.to_owned(), |glob, (key, value)| {
+                    glob.replace(&format!("{{{key}}}"), value)
+                });
+                Ok(Pattern::Glob(glob.to_lowercase()))
+            }
+        }
+    }
+
+    pub fn is_match(&self, name: &str) -> bool {
+        match self {
+            Pattern::Glob(glob) => glob_match(glob, &name.to_lowercase()),
+            Pattern::Regex(regex) => regex.is_match(name),
+        }
+    }
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Pattern::Glob(glob) => write!(f, "{glob}"),
+            Pattern::Regex(regex) => write!(f, "/{regex}/"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VARS: &[(&str, &str)] = &[("version", "1.2.3"), ("os", "linux"), ("arch", "x86_64")];
+
+    #[test]
+    fn glob_patterns() {
+        let pattern = Pattern::new("tool-{version}-{arch}-*.tar.gz", VARS).unwrap();
+        assert_eq!(pattern.to_string(), "tool-1.2.3-x86_64-*.tar.gz");
+        assert!(pattern.is_match("tool-1.2.3-x86_64-unknown-linux-musl.tar.gz"));
+        assert!(pattern.is_match("Tool-1.2.3-X86_64-Linux.tar.gz"));
+        assert!(!pattern.is_match("tool-1.2.3-x86_64-unknown-linux-musl.tar.gz.sha256"));
+        assert!(!pattern.is_match("tool-1.2.4-x86_64-linux.tar.gz"));
+    }
+
+    #[test]
+    fn regex_patterns() {
+        let pattern = Pattern::new("/^tool_{version}_{os}_(amd64|x86_64)$/", VARS).unwrap();
+        assert_eq!(pattern.to_string(), r"/^tool_1\.2\.3_linux_(amd64|x86_64)$/");
+        assert!(pattern.is_match("tool_1.2.3_linux_amd64"));
+        assert!(!pattern.is_match("tool_1x2x3_linux_amd64"));
+        // regex patterns are case-sensitive and not anchored unless specified
+        assert!(!pattern.is_match("tool_1.2.3_Linux_amd64"));
+        assert!(Pattern::new("/musl/", VARS).unwrap().is_match("tool-musl.tar.gz"));
+    }
+
+    #[test]
+    fn invalid_regex_patterns() {
+        let err = Pattern::new("/tool_(/", VARS).unwrap_err();
+        assert_eq!(err.to_string(), "Invalid regex pattern: `/tool_(/`");
+        // a single slash is a glob
+        assert!(matches!(Pattern::new("/", VARS).unwrap(), Pattern::Glob(_)));
+    }
+}
@@ -0,0 +1,175 @@
+//! Platforms to resolve assets for.
+
+use std::{
+    env::consts::{ARCH, OS},
+    fmt,
+    path::Path,
+    process::Command,
+    str::FromStr,
+    sync::LazyLock,
+};
+
+use anyhow::{bail, Error, Result};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use super::{elf::Elf, CRATE_TARGET};
+
+static HOST_LIBC: LazyLock<Option<Libc>> = LazyLock::new(|| {
+    if OS != "linux" {
+        return None;
+    }
+    let libc = Libc::from_loader("/bin/sh").or_else(Libc::from_ldd);
+    debug!("detected libc: {libc:?}");
+    libc
+});
+
+/// A platform written like `linux-x86_64` or `macos-aarch64`, with the names of [`std::env::consts`], optionally
+/// followed by the libc of linux like `linux-aarch64-musl`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Target {
+    pub os:   String,
+    pub arch: String,
+    pub libc: Option<Libc>,
+}
+
+/// The C standard library the binaries of linux are linked against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Libc {
+    Gnu,
+    Musl,
+}
+
+impl Target {
+    /// The platform rpk is running on.
+    pub fn host() -> Self {
+        Self { os: OS.to_owned(), arch: ARCH.to_owned(), libc: None }
+    }
+
+    /// Check if it's the platform rpk is running on, whatever its libc is.
+    pub fn is_host(&self) -> bool {
+        self.os == OS && self.arch == ARCH
+    }
+
+    /// Check if both are the same platform, ignoring the libc if either one doesn't specify it.
+    pub fn matches(&self, other: &Target) -> bool {
+        self.os == other.os
+            && self.arch == other.arch
+            && (self.libc.is_none() || other.libc.is_none() || self.libc == other.libc)
+    }
+
+    /// The rust target triple of the platform, like `x86_64-unknown-linux-gnu`.
+    pub fn triple(&self) -> String {
+        if *self == Self::host() {
+            return CRATE_TARGET.to_owned();
+        }
+        match self.os.as_str() {
+            "macos" => format!("{}-apple-darwin", self.arch),
+            "linux" => format!("{}-unknown-linux-{}", self.arch, self.libc.unwrap_or(Libc::Gnu)),
+            os => format!("{}-unknown-{os}", self.arch),
+        }
+    }
+}
+
+impl Libc {
+    /// Detect the libc of the host, `None` if it's not linux or unknown.
+    pub fn detect() -> Option<Libc> {
+        *HOST_LIBC
+    }
+
+    /// Guess the libc from the dynamic loader of the given binary, like `/lib/ld-musl-x86_64.so.1`.
+    fn from_loader(path: impl AsRef<Path>) -> Option<Libc> {
+        let loader = Elf::read(path.as_ref()).ok()??.interpreter?;
+        let name = loader.file_name()?.to_str()?;
+        match name {
+            _ if name.contains("musl") => Some(Libc::Musl),
+            _ if name.starts_with("ld-linux") || name.starts_with("ld64.so") => Some(Libc::Gnu),
+            _ => None,
+        }
+    }
+
+    /// Guess the libc from the output of `ldd --version`, which musl prints to stderr.
+    fn from_ldd() -> Option<Libc> {
+        let output = Command::new("ldd").arg("--version").output().ok()?;
+        let output = [output.stdout, output.stderr].concat();
+        let output = String::from_utf8_lossy(&output).to_lowercase();
+        match output {
+            _ if output.contains("musl") => Some(Libc::Musl),
+            _ if output.contains("glibc") || output.contains("gnu libc") => Some(Libc::Gnu),
+            _ => None,
+        }
+    }
+}
+
+impl Default for Target {
+    fn default() -> Self {
+        Self::host()
+    }
+}
+
+impl FromStr for Target {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (os, arch, libc) = match s.split('-').collect::<Vec<_>>()[..] {
+            [os, arch] => (os, arch, None),
+            [os, arch, libc] => (os, arch, Some(libc)),
+            _ => bail!("invalid target `{s}`, should be like: 'linux-x86_64' or 'linux-x86_64-musl'"),
+        };
+        // the aliases often seen in asset names are accepted as well
+        let os = match os.to_lowercase().as_str() {
+            "linux" => "linux",
+            "macos" | "darwin" | "osx" => "macos",
+            _ => bail!("unsupported OS of target `{s}`, should be one of: linux, macos"),
+        };
+        let arch = match arch.to_lowercase().as_str() {
+            "x86_64" | "amd64" | "x64" => "x86_64",
+            "x86" | "i686" | "i386" => "x86",
+            "aarch64" | "arm64" => "aarch64",
+            "arm" => "arm",
+            _ => bail!("unsupported architecture of target `{s}`, should be one of: x86_64, x86, aarch64, arm"),
+        };
+        let libc = match libc.map(str::to_lowercase).as_deref() {
+            None => None,
+            Some("gnu") if os == "linux" => Some(Libc::Gnu),
+            Some("musl") if os == "linux" => Some(Libc::Musl),
+            Some(_) if os != "linux" => bail!("libc of target `{s}` is only supported on linux"),
+            Some(_) => bail!("unsupported libc of target `{s}`, should be one of: gnu, musl"),
+        };
+        Ok(Self { os: os.to_owned(), arch: arch.to_owned(), libc })
+    }
+}
+
+impl TryFrom<String> for Target {
+    type Error = Error;
+
+    fn try_from(s: String) -> Result<Self> {
+        s.parse()
+    }
+}
+
+impl From<Target> for String {
+    fn from(target: Target) -> Self {
+        target.to_string()
+    }
+}
+
+impl fmt::Display for Target {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.os, self.arch)?;
+        match self.libc {
+            Some(libc) => write!(f, "-{libc}"),
+            None => Ok(()),
+        }
+    }
+}
+
+impl fmt::Display for Libc {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Libc::Gnu => write!(f, "gnu"),
+            Libc::Musl => write!(f, "musl"),
+        }
+    }
+}
@@ -18,12 +18,7 @@ pub struct TempFile {
 impl TempFile {
     pub fn new_force(orig_path: impl AsRef<Path>) -> anyhow::Result<Self> {
         let orig_path = orig_path.as_ref().to_owned();
-        let dir = orig_path.parent().context("no parent directory")?;
-        let orig_name = orig_path.file_name().context("no filename")?;
-        let mut temp_name = OsString::from("~");
-        temp_name.push(orig_name);
-
-        let temp_path = dir.join(&temp_name);
+        let temp_path = temp_path(&orig_path, "")?;
         rm_rf(&temp_path)?;
 
         let temp_file =
@@ -50,3 +45,122 @@ impl Drop for TempFile {
         rm_rf(&self.temp_path).expect("failed to delete temporary path");
     }
 }
+
+/// A temporary directory next to the original one, which replaces it when persisted.
+pub struct TempDir {
+    temp_path: PathBuf,
+    orig_path: PathBuf,
+}
+
+impl TempDir {
+    pub fn new_force(orig_path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let orig_path = orig_path.as_ref().to_owned();
+        let temp_path = temp_path(&orig_path, "")?;
+        rm_rf(&temp_path)?;
+
+        fs::create_dir_all(&temp_path)
+            .with_context(|| format!("failed to create temporary directory: {:?}", temp_path))?;
+        Ok(Self { temp_path, orig_path })
+    }
+
+    /// Replace the original directory, which is restored if the replacement fails.
+    pub fn persist(self) -> anyhow::Result<()> {
+        // directories can't be renamed over non-empty ones, so the original one is moved aside first
+        let backup_path = temp_path(&self.orig_path, ".old")?;
+        // an interruption after moving the original one aside leaves the backup as the only copy of it
+        if self.orig_path.symlink_metadata().is_err() && backup_path.symlink_metadata().is_ok() {
+            fs::rename(&backup_path, &self.orig_path)
+                .with_context(|| format!("failed to restore {:?} from its backup", self.orig_path))?;
+        }
+        rm_rf(&backup_path)?;
+
+        let exists = self.orig_path.symlink_metadata().is_ok();
+        if exists {
+            fs::rename(&self.orig_path, &backup_path)?;
+        }
+        if let Err(e) = fs::rename(&self.temp_path, &self.orig_path) {
+            let e = anyhow::Error::new(e).context(format!("failed to replace {:?}", self.orig_path));
+            return match exists {
+                true => match fs::rename(&backup_path, &self.orig_path) {
+                    Ok(()) => Err(e),
+                    Err(restore) => Err(e.context(format!(
+                        "failed to restore {:?} from its backup: {restore}",
+                        self.orig_path
+                    ))),
+                },
+                false => Err(e),
+            };
+        }
+        rm_rf(&backup_path)?;
+        Ok(())
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.temp_path
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        rm_rf(&self.temp_path).expect("failed to delete temporary path");
+    }
+}
+
+/// The temporary path next to the original one, like `~name` or `~name.old`.
+fn temp_path(orig_path: &Path, suffix: &str) -> anyhow::Result<PathBuf> {
+    let dir = orig_path.parent().context("no parent directory")?;
+    let orig_name = orig_path.file_name().context("no filename")?;
+    let mut temp_name = OsString::from("~");
+    temp_name.push(orig_name);
+    temp_name.push(suffix);
+    Ok(dir.join(temp_name))
+}
+
+/// A scratch directory for the tests, removed when dropped.
+#[cfg(test)]
+pub fn scratch(name: &str) -> TempDir {
+    let dir = std::env::temp_dir().join(format!("rpk-test-{name}-{}", std::process::id()));
+    TempDir::new_force(dir).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn persist_replaces_the_original_dir() {
+        let tmp = scratch("persist");
+        let dir = tmp.path();
+        let orig = dir.join("1.0");
+        fs::create_dir(&orig).unwrap();
+        fs::write(orig.join("old"), "").unwrap();
+
+        let temp = TempDir::new_force(&orig).unwrap();
+        fs::write(temp.path().join("new"), "").unwrap();
+        temp.persist().unwrap();
+
+        assert!(orig.join("new").exists());
+        assert!(!orig.join("old").exists());
+        assert_eq!(fs::read_dir(dir).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn persist_restores_an_interrupted_backup() {
+        let tmp = scratch("persist-interrupted");
+        let dir = tmp.path();
+        let orig = dir.join("1.0");
+        // interrupted after the original one was moved aside
+        fs::create_dir(dir.join("~1.0.old")).unwrap();
+        fs::write(dir.join("~1.0.old/old"), "").unwrap();
+
+        let temp = TempDir::new_force(&orig).unwrap();
+        fs::write(temp.path().join("new"), "").unwrap();
+        fs::remove_dir_all(temp.path()).unwrap();
+        let err = temp.persist().unwrap_err();
+
+        // the replacement fails as the new one is gone, and the original one is kept
+        assert_eq!(err.to_string(), format!("failed to replace {orig:?}"));
+        assert!(orig.join("old").exists());
+        assert_eq!(fs::read_dir(dir).unwrap().count(), 1);
+    }
+}
@@ -0,0 +1,96 @@
+//! Version constraints of packages.
+
+use anyhow::{Context as _, Result};
+use semver::{Version, VersionReq};
+
+/// Parse the version as a semver constraint like `^1.4`, `~0.23` or `>=2, <3`.
+///
+/// Returns `None` if the version is an exact tag, e.g. `v1.2.3` or `1.2.3`, and an error if it looks like a constraint
+/// but is not a valid one, e.g. `^foo`.
+pub fn parse_constraint(version: &str) -> Result<Option<VersionReq>> {
+    let is_constraint = version.starts_with(['^', '~', '<', '>', '=', '*'])
+        || version.contains([',', '*'])
+        || version.split('.').any(|part| part == "x" || part == "X");
+    match is_constraint {
+        true => VersionReq::parse(version)
+            .map(Some)
+            .with_context(|| format!("invalid version constraint: `{version}`")),
+        false => Ok(None),
+    }
+}
+
+/// Parse the version from a release tag, ignoring the prefix like `v1.2.3`, `release-1.2.3` or `fd-v1.2.3`.
+pub fn parse_tag(tag: &str) -> Option<Version> {
+    tag.char_indices()
+        // a version starts at a number, not in the middle of one or of another version
+        .filter(|&(i, c)| c.is_ascii_digit() && !tag[..i].ends_with(|c: char| c.is_ascii_digit() || c == '.'))
+        .find_map(|(i, _)| parse_lenient(&tag[i..]))
+}
+
+/// Parse a version which may omit the patch number, like `1.2` or `1.2-beta`.
+fn parse_lenient(s: &str) -> Option<Version> {
+    if let Ok(version) = Version::parse(s) {
+        return Some(version);
+    }
+
+    let end = s.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(s.len());
+    let (core, rest) = s.split_at(end);
+    if !(rest.is_empty() || rest.starts_with(['-', '+'])) {
+        return None;
+    }
+    match core.split('.').collect::<Vec<_>>()[..] {
+        [major, minor] if !major.is_empty() && !minor.is_empty() => Version::parse(&format!("{core}.0{rest}")).ok(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_constraints() {
+        for version in ["^1.4", "~0.23", ">=2, <3", "=1.2.3", "*", "1.*", "1.x", "1.2.X"] {
+            assert!(parse_constraint(version).unwrap().is_some(), "{version}");
+        }
+        let req = parse_constraint("^1.4").unwrap().unwrap();
+        assert!(req.matches(&Version::new(1, 9, 0)));
+        assert!(!req.matches(&Version::new(2, 0, 0)));
+    }
+
+    #[test]
+    fn exact_tags_are_not_constraints() {
+        for version in ["v1.2.3", "1.2.3", "nightly", "release-1.2", "x86"] {
+            assert!(parse_constraint(version).unwrap().is_none(), "{version}");
+        }
+    }
+
+    #[test]
+    fn reject_malformed_constraints() {
+        for version in ["^foo", ">=1.x.y", "~", "1.x, foo"] {
+            let err = parse_constraint(version).unwrap_err();
+            assert_eq!(err.to_string(), format!("invalid version constraint: `{version}`"));
+        }
+    }
+
+    #[test]
+    fn parse_tags() {
+        let version = |tag| parse_tag(tag).map(|v| v.to_string());
+        assert_eq!(version("1.2.3").as_deref(), Some("1.2.3"));
+        assert_eq!(version("v1.2.3").as_deref(), Some("1.2.3"));
+        assert_eq!(version("release-1.2.3").as_deref(), Some("1.2.3"));
+        assert_eq!(version("fd-v1.2.3").as_deref(), Some("1.2.3"));
+        assert_eq!(version("v2.0.0-beta.1").as_deref(), Some("2.0.0-beta.1"));
+        assert_eq!(version("v1.2").as_deref(), Some("1.2.0"));
+        assert_eq!(version("v1.2-rc1").as_deref(), Some("1.2.0-rc1"));
+        // the version is found after the digits in the prefix
+        assert_eq!(version("x86_64-v1.2.3").as_deref(), Some("1.2.3"));
+    }
+
+    #[test]
+    fn parse_tags_without_versions() {
+        for tag in ["nightly", "latest", "v1", "v1.2.3.4", "1.2beta"] {
+            assert_eq!(parse_tag(tag), None, "{tag}");
+        }
+    }
+}